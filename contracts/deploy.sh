@@ -1,7 +1,7 @@
 #!/bin/bash
 
 # Relifo Contracts Deployment Script
-# Deploys all 4 contracts to Stellar Testnet
+# Deploys all 5 contracts to Stellar Testnet
 # Admin Address: GBDD6IDWYK5XM77GYSPKW7BC2KY3D4DPNP3MFQVHZJ3BCWMHB3T7NDWT
 
 set -e
@@ -46,6 +46,10 @@ echo "  - MerchantRegistry"
 cargo build --release --target wasm32-unknown-unknown --features merchant --quiet
 cp target/wasm32-unknown-unknown/release/relifo_contracts.wasm target/wasm32-unknown-unknown/release/merchant.wasm
 
+echo "  - CategoryRegistry"
+cargo build --release --target wasm32-unknown-unknown --features category --quiet
+cp target/wasm32-unknown-unknown/release/relifo_contracts.wasm target/wasm32-unknown-unknown/release/category.wasm
+
 echo "✅ All contracts built"
 echo ""
 
@@ -85,6 +89,13 @@ MERCHANT_WASM_HASH=$(stellar contract install \
   --source $ADMIN_ADDRESS 2>&1 | tail -1)
 echo "    Hash: $MERCHANT_WASM_HASH"
 
+echo "  Installing Category WASM..."
+CATEGORY_WASM_HASH=$(stellar contract install \
+  --wasm target/wasm32-unknown-unknown/release/category.wasm \
+  --network $NETWORK \
+  --source $ADMIN_ADDRESS 2>&1 | tail -1)
+echo "    Hash: $CATEGORY_WASM_HASH"
+
 echo ""
 echo "✅ All WASM files installed"
 echo ""
@@ -120,6 +131,13 @@ MERCHANT_ID=$(stellar contract deploy \
   --network $NETWORK 2>&1 | tail -1)
 echo "    Contract ID: $MERCHANT_ID"
 
+echo "  Deploying CategoryRegistry..."
+CATEGORY_ID=$(stellar contract deploy \
+  --wasm-hash $CATEGORY_WASM_HASH \
+  --source $ADMIN_ADDRESS \
+  --network $NETWORK 2>&1 | tail -1)
+echo "    Contract ID: $CATEGORY_ID"
+
 echo ""
 echo "✅ All contracts deployed"
 echo ""
@@ -132,6 +150,7 @@ NEXT_PUBLIC_VAULT_CONTRACT_ID=$VAULT_ID
 NEXT_PUBLIC_NGO_CONTRACT_ID=$NGO_ID
 NEXT_PUBLIC_BENEFICIARY_CONTRACT_ID=$BENEFICIARY_ID
 NEXT_PUBLIC_MERCHANT_CONTRACT_ID=$MERCHANT_ID
+NEXT_PUBLIC_CATEGORY_CONTRACT_ID=$CATEGORY_ID
 
 # Admin Address
 NEXT_PUBLIC_ADMIN_ADDRESS=$ADMIN_ADDRESS
@@ -171,6 +190,9 @@ echo ""
 echo "MerchantRegistry Contract:"
 echo "  $MERCHANT_ID"
 echo ""
+echo "CategoryRegistry Contract:"
+echo "  $CATEGORY_ID"
+echo ""
 echo "Admin Address:"
 echo "  $ADMIN_ADDRESS"
 echo ""
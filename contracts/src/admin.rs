@@ -0,0 +1,70 @@
+use soroban_sdk::{contracttype, Address, Env};
+use crate::error::Error;
+use crate::event;
+
+// Two-step admin transfer shared by every Relifo contract
+//
+// The current admin proposes a successor, who must then accept with their
+// own signature. Until then the current admin stays in charge, so a typo
+// in the new address cannot lock the contract.
+
+/// Admin proposed by the current admin and not yet accepted
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAdmin {
+    pub address: Address,
+    pub proposed_by: Address,
+    /// Ledger timestamp after which the proposal lapses; 0 means never
+    pub expires_at: u64,
+}
+
+/// Admin transfer state, kept in instance storage
+#[contracttype]
+#[derive(Clone)]
+enum AdminKey {
+    Pending,
+}
+
+/// Record `new_admin` as the proposed successor, replacing any earlier proposal
+///
+/// The caller must already have checked the current admin's authorization.
+pub fn propose(env: &Env, current_admin: Address, new_admin: Address, expires_at: u64) -> Result<(), Error> {
+    if expires_at != 0 && expires_at <= env.ledger().timestamp() {
+        return Err(Error::InvalidDeadline);
+    }
+
+    let pending = PendingAdmin {
+        address: new_admin.clone(),
+        proposed_by: current_admin.clone(),
+        expires_at,
+    };
+    env.storage().instance().set(&AdminKey::Pending, &pending);
+
+    event::emit_admin_proposed(env, current_admin.clone(), new_admin, expires_at);
+    event::emit_audit_trail(env, event::ADMIN_PROPOSED, current_admin);
+
+    Ok(())
+}
+
+/// Take the pending proposal once the proposed admin has signed
+///
+/// Returns the new admin for the contract to store.
+pub fn accept(env: &Env) -> Result<Address, Error> {
+    let pending = get_pending(env).ok_or(Error::NoPendingAdmin)?;
+    pending.address.require_auth();
+
+    if pending.expires_at != 0 && env.ledger().timestamp() > pending.expires_at {
+        return Err(Error::AdminProposalExpired);
+    }
+    env.storage().instance().remove(&AdminKey::Pending);
+
+    event::emit_admin_changed(env, pending.proposed_by, pending.address.clone());
+    event::emit_audit_trail(env, event::ADMIN_CHANGED, pending.address.clone());
+
+    Ok(pending.address)
+}
+
+/// Get the proposed admin, if any
+pub fn get_pending(env: &Env) -> Option<PendingAdmin> {
+    env.storage().instance().get(&AdminKey::Pending)
+}
@@ -1,12 +1,36 @@
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, String, Symbol, symbol_short, Vec};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map, String, Symbol, symbol_short, Vec};
+use crate::admin::{self, PendingAdmin};
 use crate::error::Error;
 use crate::event;
+use crate::pause;
+use crate::rbac;
+use crate::registry::{CategoryRegistryClient, VaultClient};
+use crate::ttl::{self, TtlConfig};
+use crate::types::{Category, Operation, PauseScope, Role};
+use crate::upgrade;
 
 // Storage keys (max 9 chars for symbol_short!)
 const ADMIN: Symbol = symbol_short!("ADMIN");
-const BENEFS: Symbol = symbol_short!("BENEFS");
+const BENEFS: Symbol = symbol_short!("REGS");
 const PENDING: Symbol = symbol_short!("PENDING");
 const INIT: Symbol = symbol_short!("INIT");
+const VAULT: Symbol = symbol_short!("VAULT");
+const CATEGORIES: Symbol = symbol_short!("CAT_REG");
+// Shared limits key written before limits were stored per beneficiary
+const LEGACY_LIMITS: Symbol = symbol_short!("LIM");
+// Beneficiaries keyed by address alone, written before registrations were per campaign
+const LEGACY_BENEFS: Symbol = symbol_short!("BENEFS");
+
+/// Storage layout version written by this code
+const SCHEMA_VERSION: u32 = 2;
+
+/// Per-registration storage keys
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// Category limits of one beneficiary in one campaign
+    Limits(String, Address),
+}
 
 /// Beneficiary Status enum
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -23,7 +47,7 @@ pub enum BeneficiaryStatus {
 #[derive(Clone)]
 #[contracttype]
 pub struct CategoryLimit {
-    pub category: Symbol,
+    pub category: Category,
     pub limit: i128,
     pub spent: i128,
 }
@@ -54,7 +78,7 @@ impl BeneficiaryRegistry {
     /// Initialize the beneficiary registry
     pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
         // Check if already initialized
-        if env.storage().instance().has(&INIT) {
+        if env.storage().instance().has(&INIT) || upgrade::has_version(&env) {
             return Err(Error::AlreadyInitialized);
         }
 
@@ -64,8 +88,8 @@ impl BeneficiaryRegistry {
         // Store admin
         env.storage().instance().set(&ADMIN, &admin);
 
-        // Initialize empty beneficiary map
-        let benefs: Map<Address, BeneficiaryInfo> = Map::new(&env);
+        // Initialize empty registration map ((campaign_id, beneficiary) -> info)
+        let benefs: Map<(String, Address), BeneficiaryInfo> = Map::new(&env);
         env.storage().instance().set(&BENEFS, &benefs);
 
         // Initialize pending applications map (campaign_id -> Vec<Address>)
@@ -74,6 +98,11 @@ impl BeneficiaryRegistry {
 
         // Mark as initialized
         env.storage().instance().set(&INIT, &true);
+        upgrade::set_version(&env, SCHEMA_VERSION);
+        ttl::extend_instance(&env);
+
+        event::emit_config_updated(&env, symbol_short!("admin"), admin.clone(), admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
 
         Ok(())
     }
@@ -85,17 +114,21 @@ impl BeneficiaryRegistry {
         campaign_id: String,
         mongodb_doc_id: String,
     ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
         // Beneficiary must authorize their own registration
         beneficiary.require_auth();
 
         // Get beneficiary map
-        let mut benefs: Map<Address, BeneficiaryInfo> = env.storage()
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
             .instance()
             .get(&BENEFS)
             .unwrap_or(Map::new(&env));
 
-        // Check if already registered
-        if benefs.contains_key(beneficiary.clone()) {
+        // Check if already registered for this campaign
+        let key = (campaign_id.clone(), beneficiary.clone());
+        if benefs.contains_key(key.clone()) {
             return Err(Error::AlreadyInitialized);
         }
 
@@ -117,7 +150,7 @@ impl BeneficiaryRegistry {
         };
 
         // Store beneficiary
-        benefs.set(beneficiary.clone(), benef_info);
+        benefs.set(key, benef_info);
         env.storage().instance().set(&BENEFS, &benefs);
 
         // Add to pending list for this campaign
@@ -133,38 +166,53 @@ impl BeneficiaryRegistry {
         env.storage().instance().set(&PENDING, &pending);
 
         // Emit registration event
-        let pending_mode = String::from_str(&env, "PENDING");
-        event::emit_beneficiary_whitelisted(&env, campaign_id, beneficiary, pending_mode);
+        event::emit_beneficiary_registered(&env, campaign_id.clone(), beneficiary.clone());
+        event::emit_campaign_audit_trail(&env, event::BENEFICIARY_REGISTERED, campaign_id, beneficiary);
 
         Ok(())
     }
 
-    /// NGO approves beneficiary with category limits
+    /// Approve a beneficiary's registration for a campaign with category
+    /// limits (campaign NGO, field agent or admin)
     pub fn approve_beneficiary(
         env: Env,
-        ngo_address: Address,
+        caller: Address,
         beneficiary: Address,
-        categories: Vec<Symbol>,
+        campaign_id: String,
+        categories: Vec<Category>,
         limits: Vec<i128>,
     ) -> Result<i128, Error> {
-        // NGO must authorize
-        ngo_address.require_auth();
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
 
         // Get beneficiary map
-        let mut benefs: Map<Address, BeneficiaryInfo> = env.storage()
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
             .instance()
             .get(&BENEFS)
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
         // Get beneficiary info
-        let mut benef_info = benefs.get(beneficiary.clone())
+        let key = (campaign_id.clone(), beneficiary.clone());
+        let mut benef_info = benefs.get(key.clone())
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
+        // Caller must run the beneficiary's campaign
+        Self::require_campaign_ngo(&env, &caller, &campaign_id)?;
+        let categories = Category::normalize_all(&env, &categories)?;
+
         // Verify status is pending
         if benef_info.status != BeneficiaryStatus::Pending {
             return Err(Error::InvalidControlMode);
         }
 
+        // Every category must be active in the taxonomy
+        let category_registry = Self::category_registry(&env)?;
+        for category in categories.iter() {
+            if !category_registry.is_active(&category) {
+                return Err(Error::InvalidCategory);
+            }
+        }
+
         // Calculate total allocation from limits
         let mut total: i128 = 0;
         for limit in limits.iter() {
@@ -177,12 +225,12 @@ impl BeneficiaryRegistry {
         benef_info.total_allocation = total;
 
         // Save updated info
-        benefs.set(beneficiary.clone(), benef_info.clone());
+        benefs.set(key, benef_info.clone());
         env.storage().instance().set(&BENEFS, &benefs);
 
         // Store category limits separately
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let mut cat_limits: Map<Symbol, CategoryLimit> = Map::new(&env);
+        let limits_key = DataKey::Limits(campaign_id.clone(), beneficiary.clone());
+        let mut cat_limits: Map<Category, CategoryLimit> = Map::new(&env);
         
         for i in 0..categories.len() {
             let cat = categories.get(i).unwrap();
@@ -193,10 +241,10 @@ impl BeneficiaryRegistry {
                 spent: 0,
             });
         }
-        env.storage().instance().set(&limits_key, &cat_limits);
+        env.storage().persistent().set(&limits_key, &cat_limits);
+        ttl::extend_persistent(&env, &limits_key);
 
         // Remove from pending list
-        let campaign_id = benef_info.campaign_id.clone();
         let mut pending: Map<String, Vec<Address>> = env.storage()
             .instance()
             .get(&PENDING)
@@ -210,37 +258,49 @@ impl BeneficiaryRegistry {
                     new_pending.push_back(addr);
                 }
             }
-            pending.set(campaign_id, new_pending);
+            pending.set(campaign_id.clone(), new_pending);
             env.storage().instance().set(&PENDING, &pending);
         }
 
         // Emit approval event
-        let controlled_mode = String::from_str(&env, "CONTROLLED");
-        event::emit_beneficiary_whitelisted(&env, benef_info.campaign_id, beneficiary, controlled_mode);
+        event::emit_beneficiary_approved(
+            &env,
+            campaign_id.clone(),
+            beneficiary,
+            caller.clone(),
+            categories,
+            total,
+        );
+        event::emit_campaign_audit_trail(&env, event::BENEFICIARY_APPROVED, campaign_id, caller);
 
         Ok(total)
     }
 
-    /// NGO rejects beneficiary application
+    /// Reject a beneficiary's application to a campaign (campaign NGO, field agent or admin)
     pub fn reject_beneficiary(
         env: Env,
-        ngo_address: Address,
+        caller: Address,
         beneficiary: Address,
+        campaign_id: String,
         reason: String,
     ) -> Result<(), Error> {
-        // NGO must authorize
-        ngo_address.require_auth();
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
 
         // Get beneficiary map
-        let mut benefs: Map<Address, BeneficiaryInfo> = env.storage()
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
             .instance()
             .get(&BENEFS)
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
         // Get beneficiary info
-        let mut benef_info = benefs.get(beneficiary.clone())
+        let key = (campaign_id.clone(), beneficiary.clone());
+        let mut benef_info = benefs.get(key.clone())
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
+        // Caller must run the beneficiary's campaign
+        Self::require_campaign_ngo(&env, &caller, &campaign_id)?;
+
         // Verify status is pending
         if benef_info.status != BeneficiaryStatus::Pending {
             return Err(Error::InvalidControlMode);
@@ -251,11 +311,10 @@ impl BeneficiaryRegistry {
         benef_info.rejection_reason = reason;
 
         // Save updated info
-        benefs.set(beneficiary.clone(), benef_info.clone());
+        benefs.set(key, benef_info.clone());
         env.storage().instance().set(&BENEFS, &benefs);
 
         // Remove from pending list
-        let campaign_id = benef_info.campaign_id.clone();
         let mut pending: Map<String, Vec<Address>> = env.storage()
             .instance()
             .get(&PENDING)
@@ -268,61 +327,85 @@ impl BeneficiaryRegistry {
                     new_pending.push_back(addr);
                 }
             }
-            pending.set(campaign_id, new_pending);
+            pending.set(campaign_id.clone(), new_pending);
             env.storage().instance().set(&PENDING, &pending);
         }
 
         // Emit rejection event
-        let rejection_reason = String::from_str(&env, "REJECTED");
-        event::emit_beneficiary_revoked(&env, benef_info.campaign_id, beneficiary, rejection_reason);
+        event::emit_beneficiary_rejected(
+            &env,
+            campaign_id.clone(),
+            beneficiary,
+            caller.clone(),
+            benef_info.rejection_reason,
+        );
+        event::emit_campaign_audit_trail(&env, event::BENEFICIARY_REJECTED, campaign_id, caller);
 
         Ok(())
     }
 
-    /// Revoke beneficiary access
+    /// Revoke a beneficiary's access to a campaign (campaign NGO, field agent or admin)
     pub fn revoke_beneficiary(
         env: Env,
-        ngo_address: Address,
+        caller: Address,
         beneficiary: Address,
+        campaign_id: String,
     ) -> Result<(), Error> {
-        // NGO must authorize
-        ngo_address.require_auth();
+        ttl::extend_instance(&env);
 
         // Get beneficiary map
-        let mut benefs: Map<Address, BeneficiaryInfo> = env.storage()
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
             .instance()
             .get(&BENEFS)
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
         // Get beneficiary info
-        let mut benef_info = benefs.get(beneficiary.clone())
+        let key = (campaign_id.clone(), beneficiary.clone());
+        let mut benef_info = benefs.get(key.clone())
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
-        // Save campaign_id before moving benef_info
-        let campaign_id = benef_info.campaign_id.clone();
+        // Caller must run the beneficiary's campaign
+        Self::require_campaign_ngo(&env, &caller, &campaign_id)?;
 
         // Update status
         benef_info.status = BeneficiaryStatus::Suspended;
 
         // Save updated info
-        benefs.set(beneficiary.clone(), benef_info);
+        benefs.set(key, benef_info);
         env.storage().instance().set(&BENEFS, &benefs);
 
         // Emit revocation event
-        let revoke_reason = String::from_str(&env, "REVOKED");
-        event::emit_beneficiary_revoked(&env, campaign_id, beneficiary, revoke_reason);
+        event::emit_beneficiary_revoked(&env, campaign_id.clone(), beneficiary, caller.clone());
+        event::emit_campaign_audit_trail(&env, event::BENEFICIARY_REVOKED, campaign_id, caller);
 
         Ok(())
     }
 
-    /// Check if beneficiary is approved
+    /// Check if beneficiary is approved for any campaign
     pub fn is_approved(env: Env, beneficiary: Address) -> bool {
-        let benefs: Map<Address, BeneficiaryInfo> = match env.storage().instance().get(&BENEFS) {
+        ttl::extend_instance(&env);
+
+        let benefs: Map<(String, Address), BeneficiaryInfo> = match env.storage().instance().get(&BENEFS) {
+            Some(b) => b,
+            None => return false,
+        };
+
+        benefs.values().iter().any(|info| {
+            info.address == beneficiary && info.status == BeneficiaryStatus::Approved
+        })
+    }
+
+    /// Check if beneficiary is approved for a campaign
+    pub fn is_approved_for_campaign(env: Env, beneficiary: Address, campaign_id: String) -> bool {
+        ttl::extend_instance(&env);
+        ttl::extend_persistent(&env, &DataKey::Limits(campaign_id.clone(), beneficiary.clone()));
+
+        let benefs: Map<(String, Address), BeneficiaryInfo> = match env.storage().instance().get(&BENEFS) {
             Some(b) => b,
             None => return false,
         };
 
-        match benefs.get(beneficiary) {
+        match benefs.get((campaign_id, beneficiary)) {
             Some(info) => info.status == BeneficiaryStatus::Approved,
             None => false,
         }
@@ -333,10 +416,10 @@ impl BeneficiaryRegistry {
         Self::is_approved(env, beneficiary)
     }
 
-    /// Get application status
-    pub fn get_application_status(env: Env, beneficiary: Address) -> Option<BeneficiaryInfo> {
-        let benefs: Map<Address, BeneficiaryInfo> = env.storage().instance().get(&BENEFS)?;
-        benefs.get(beneficiary)
+    /// Get the status of a beneficiary's application to a campaign
+    pub fn get_application_status(env: Env, beneficiary: Address, campaign_id: String) -> Option<BeneficiaryInfo> {
+        let benefs: Map<(String, Address), BeneficiaryInfo> = env.storage().instance().get(&BENEFS)?;
+        benefs.get((campaign_id, beneficiary))
     }
 
     /// Get pending applications for a campaign
@@ -346,19 +429,19 @@ impl BeneficiaryRegistry {
             None => return Vec::new(&env),
         };
 
-        let benefs: Map<Address, BeneficiaryInfo> = match env.storage().instance().get(&BENEFS) {
+        let benefs: Map<(String, Address), BeneficiaryInfo> = match env.storage().instance().get(&BENEFS) {
             Some(b) => b,
             None => return Vec::new(&env),
         };
 
-        let addresses = match pending.get(campaign_id) {
+        let addresses = match pending.get(campaign_id.clone()) {
             Some(a) => a,
             None => return Vec::new(&env),
         };
 
         let mut result: Vec<BeneficiaryInfo> = Vec::new(&env);
         for addr in addresses.iter() {
-            if let Some(info) = benefs.get(addr) {
+            if let Some(info) = benefs.get((campaign_id.clone(), addr)) {
                 result.push_back(info);
             }
         }
@@ -367,150 +450,319 @@ impl BeneficiaryRegistry {
 
     /// Get approved beneficiaries for a campaign
     pub fn get_approved_beneficiaries(env: Env, campaign_id: String) -> Vec<Address> {
-        let benefs: Map<Address, BeneficiaryInfo> = match env.storage().instance().get(&BENEFS) {
+        let benefs: Map<(String, Address), BeneficiaryInfo> = match env.storage().instance().get(&BENEFS) {
             Some(b) => b,
             None => return Vec::new(&env),
         };
 
         let mut result: Vec<Address> = Vec::new(&env);
-        for (addr, info) in benefs.iter() {
-            if info.status == BeneficiaryStatus::Approved && info.campaign_id == campaign_id {
+        for ((id, addr), info) in benefs.iter() {
+            if info.status == BeneficiaryStatus::Approved && id == campaign_id {
                 result.push_back(addr);
             }
         }
         result
     }
 
-    /// Get category limit for beneficiary
-    pub fn get_category_limit(env: Env, beneficiary: Address, category: Symbol) -> i128 {
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let cat_limits: Map<Symbol, CategoryLimit> = match env.storage().instance().get(&limits_key) {
-            Some(l) => l,
-            None => return 0,
-        };
-
-        match cat_limits.get(category) {
+    /// Get a beneficiary's category limit in a campaign
+    pub fn get_category_limit(env: Env, beneficiary: Address, campaign_id: String, category: Category) -> i128 {
+        match Self::category_limit(&env, &beneficiary, &campaign_id, &category) {
             Some(limit) => limit.limit,
             None => 0,
         }
     }
 
-    /// Get category spent for beneficiary
-    pub fn get_category_spent(env: Env, beneficiary: Address, category: Symbol) -> i128 {
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let cat_limits: Map<Symbol, CategoryLimit> = match env.storage().instance().get(&limits_key) {
-            Some(l) => l,
-            None => return 0,
-        };
-
-        match cat_limits.get(category) {
+    /// Get a beneficiary's category spending in a campaign
+    pub fn get_category_spent(env: Env, beneficiary: Address, campaign_id: String, category: Category) -> i128 {
+        match Self::category_limit(&env, &beneficiary, &campaign_id, &category) {
             Some(limit) => limit.spent,
             None => 0,
         }
     }
 
-    /// Get remaining balance in category
-    pub fn get_category_balance(env: Env, beneficiary: Address, category: Symbol) -> i128 {
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let cat_limits: Map<Symbol, CategoryLimit> = match env.storage().instance().get(&limits_key) {
-            Some(l) => l,
-            None => return 0,
-        };
-
-        match cat_limits.get(category) {
+    /// Get remaining balance in category for a campaign
+    pub fn get_category_balance(env: Env, beneficiary: Address, campaign_id: String, category: Category) -> i128 {
+        match Self::category_limit(&env, &beneficiary, &campaign_id, &category) {
             Some(limit) => limit.limit - limit.spent,
             None => 0,
         }
     }
 
-    /// Update category spending (called after transaction)
-    pub fn update_spending(
-        env: Env,
-        beneficiary: Address,
-        category: Symbol,
-        amount: i128,
-    ) -> Result<(), Error> {
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let mut cat_limits: Map<Symbol, CategoryLimit> = env.storage()
-            .instance()
-            .get(&limits_key)
-            .ok_or(Error::BeneficiaryNotWhitelisted)?;
+    /// Pause every state-changing operation, or only one (operator, auditor or admin)
+    pub fn pause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
 
-        let mut cat_limit = cat_limits.get(category.clone())
-            .ok_or(Error::CategoryLimitExceeded)?;
+        // Caller must be an operator or auditor
+        Self::require_role(&env, &caller, &[Role::Operator, Role::Auditor])?;
+
+        pause::set_paused(&env, scope, true, caller);
+        Ok(())
+    }
+
+    /// Lift a pause set for the same scope (operator or admin)
+    ///
+    /// Unpausing one operation leaves a contract-wide pause in place.
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        pause::set_paused(&env, scope, false, caller);
+        Ok(())
+    }
+
+    /// Check whether an operation may not run because of a pause
+    ///
+    /// `All` reports only a contract-wide pause.
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        pause::is_paused(&env, scope)
+    }
+
+    /// Propose a new admin, who takes over once they accept (admin only)
+    ///
+    /// `expires_at` is the ledger timestamp after which the proposal can no
+    /// longer be accepted; 0 means it never lapses. A new proposal replaces
+    /// the previous one.
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: u64) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        admin::propose(&env, admin, new_admin, expires_at)
+    }
 
-        // Check limit
-        if cat_limit.spent + amount > cat_limit.limit {
-            return Err(Error::CategoryLimitExceeded);
+    /// Accept a pending admin proposal (proposed admin only)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let new_admin = admin::accept(&env)?;
+        env.storage().instance().set(&ADMIN, &new_admin);
+        Ok(())
+    }
+
+    /// Get the current admin
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ADMIN)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<PendingAdmin> {
+        admin::get_pending(&env)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::grant(&env, role, account, admin)
+    }
+
+    /// Revoke a role from an account (admin only)
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::revoke(&env, role, account, admin)
+    }
+
+    /// Check whether an account holds a role; the admin holds every role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        match env.storage().instance().get::<_, Address>(&ADMIN) {
+            Some(admin) => rbac::has_role(&env, &admin, role, &account),
+            None => false,
         }
+    }
 
-        // Update spent
-        cat_limit.spent += amount;
-        cat_limits.set(category, cat_limit);
-        env.storage().instance().set(&limits_key, &cat_limits);
+    /// Replace this contract's code with an uploaded wasm (admin only)
+    ///
+    /// Storage is kept; call `migrate` afterwards to convert it if the new
+    /// code changed its layout.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        ttl::extend_instance(&env);
 
-        // Update total spent in beneficiary info
-        let mut benefs: Map<Address, BeneficiaryInfo> = env.storage()
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::upgrade(&env, new_wasm_hash, admin);
+        Ok(())
+    }
+
+    /// Schema version of the data in storage
+    pub fn version(env: Env) -> u32 {
+        upgrade::stored_version(&env)
+    }
+
+    /// Convert stored data to this code's schema version (admin only)
+    ///
+    /// Returns the version now in storage.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::migrate(&env, SCHEMA_VERSION, Self::migrate_step, admin)
+    }
+
+    /// Set how far and how often storage lifetimes are extended (admin only)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        ttl::set_config(&env, config, admin)
+    }
+
+    /// Get the storage lifetime settings
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl::get_config(&env)
+    }
+
+    /// Extend a beneficiary's category limits in a campaign (anyone)
+    pub fn bump_beneficiary(env: Env, beneficiary: Address, campaign_id: String) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
             .instance()
             .get(&BENEFS)
-            .ok_or(Error::BeneficiaryNotWhitelisted)?;
+            .unwrap_or(Map::new(&env));
+        if !benefs.contains_key((campaign_id.clone(), beneficiary.clone())) {
+            return Err(Error::BeneficiaryNotWhitelisted);
+        }
+        ttl::extend_persistent(&env, &DataKey::Limits(campaign_id, beneficiary));
+        Ok(())
+    }
 
-        let mut benef_info = benefs.get(beneficiary.clone())
-            .ok_or(Error::BeneficiaryNotWhitelisted)?;
+    /// Set the ReliefVault allowed to record spending and consulted for campaign owners (admin only)
+    pub fn set_vault(env: Env, vault: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
 
-        benef_info.spent += amount;
-        benefs.set(beneficiary, benef_info);
-        env.storage().instance().set(&BENEFS, &benefs);
+        let admin = Self::require_admin(&env)?;
+
+        env.storage().instance().set(&VAULT, &vault);
+
+        event::emit_config_updated(&env, symbol_short!("vault"), vault, admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
+
+        Ok(())
+    }
+
+    /// Get the ReliefVault allowed to record spending
+    pub fn get_vault(env: Env) -> Option<Address> {
+        env.storage().instance().get(&VAULT)
+    }
+
+    /// Set the CategoryRegistry categories are validated against (admin only)
+    pub fn set_category_registry(env: Env, category_registry: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        env.storage().instance().set(&CATEGORIES, &category_registry);
+
+        event::emit_config_updated(&env, symbol_short!("cat_reg"), category_registry, admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
 
         Ok(())
     }
 
-    /// Enforce category spending (check before transaction)
-    pub fn enforce_category_spending(
+    /// Get the CategoryRegistry categories are validated against
+    pub fn get_category_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&CATEGORIES)
+    }
+
+    /// Record category spending (called by the vault after a transaction)
+    ///
+    /// Spending counts against the category and every ancestor that has a
+    /// limit, so a limit on `food` also covers `infant_formula`. The vault
+    /// enforces the limits of each campaign allocation before paying, so a
+    /// spend is recorded here even where it goes past these limits or no
+    /// limit covers the category.
+    pub fn update_spending(
         env: Env,
         beneficiary: Address,
-        category: Symbol,
+        campaign_id: String,
+        category: Category,
         amount: i128,
     ) -> Result<(), Error> {
-        // Check if approved
-        if !Self::is_approved(env.clone(), beneficiary.clone()) {
-            return Err(Error::BeneficiaryNotWhitelisted);
+        pause::require_not_paused(&env, Operation::Spending)?;
+        ttl::extend_instance(&env);
+
+        // Only the authorised vault may record spending
+        let vault: Address = env.storage()
+            .instance()
+            .get(&VAULT)
+            .ok_or(Error::Unauthorized)?;
+        vault.require_auth();
+        let category = category.normalize(&env)?;
+
+        // Update every limit covering the category
+        let limits_key = DataKey::Limits(campaign_id.clone(), beneficiary.clone());
+        let limits: Option<Map<Category, CategoryLimit>> = env.storage().persistent().get(&limits_key);
+        if let Some(mut cat_limits) = limits {
+            for cat in Self::category_path(&env, &category)?.iter() {
+                if let Some(mut cat_limit) = cat_limits.get(cat.clone()) {
+                    cat_limit.spent += amount;
+                    cat_limits.set(cat, cat_limit);
+                }
+            }
+            env.storage().persistent().set(&limits_key, &cat_limits);
+            ttl::extend_persistent(&env, &limits_key);
         }
 
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let cat_limits: Map<Symbol, CategoryLimit> = env.storage()
+        // Update total spent in beneficiary info
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
             .instance()
-            .get(&limits_key)
+            .get(&BENEFS)
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
-        let cat_limit = cat_limits.get(category)
-            .ok_or(Error::CategoryLimitExceeded)?;
+        let key = (campaign_id.clone(), beneficiary.clone());
+        let mut benef_info = benefs.get(key.clone())
+            .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
-        // Check limit
-        if cat_limit.spent + amount > cat_limit.limit {
-            return Err(Error::CategoryLimitExceeded);
-        }
+        benef_info.spent += amount;
+        benefs.set(key, benef_info);
+        env.storage().instance().set(&BENEFS, &benefs);
+
+        event::emit_spending_recorded(&env, campaign_id.clone(), beneficiary, category, amount);
+        event::emit_campaign_audit_trail(&env, event::SPENDING_RECORDED, campaign_id, vault);
 
         Ok(())
     }
 
-    /// Update category limit (NGO only)
+    /// Update a beneficiary's category limit in a campaign (campaign NGO, field agent or admin)
     pub fn update_category_limit(
         env: Env,
-        ngo_address: Address,
+        caller: Address,
         beneficiary: Address,
-        category: Symbol,
+        campaign_id: String,
+        category: Category,
         new_limit: i128,
     ) -> Result<(), Error> {
-        // NGO must authorize
-        ngo_address.require_auth();
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
 
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let mut cat_limits: Map<Symbol, CategoryLimit> = env.storage()
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
             .instance()
+            .get(&BENEFS)
+            .ok_or(Error::BeneficiaryNotWhitelisted)?;
+
+        let key = (campaign_id.clone(), beneficiary.clone());
+        let mut benef_info = benefs.get(key.clone())
+            .ok_or(Error::BeneficiaryNotWhitelisted)?;
+
+        // Caller must run the beneficiary's campaign
+        Self::require_campaign_ngo(&env, &caller, &campaign_id)?;
+
+        let limits_key = DataKey::Limits(campaign_id.clone(), beneficiary.clone());
+        let mut cat_limits: Map<Category, CategoryLimit> = env.storage()
+            .persistent()
             .get(&limits_key)
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
+        let category = category.normalize(&env)?;
         let mut cat_limit = cat_limits.get(category.clone())
             .ok_or(Error::CategoryLimitExceeded)?;
 
@@ -520,29 +772,33 @@ impl BeneficiaryRegistry {
 
         // Update limit
         cat_limit.limit = new_limit;
-        cat_limits.set(category, cat_limit);
-        env.storage().instance().set(&limits_key, &cat_limits);
+        cat_limits.set(category.clone(), cat_limit);
+        env.storage().persistent().set(&limits_key, &cat_limits);
+        ttl::extend_persistent(&env, &limits_key);
 
         // Update total allocation
-        let mut benefs: Map<Address, BeneficiaryInfo> = env.storage()
-            .instance()
-            .get(&BENEFS)
-            .ok_or(Error::BeneficiaryNotWhitelisted)?;
-
-        let mut benef_info = benefs.get(beneficiary.clone())
-            .ok_or(Error::BeneficiaryNotWhitelisted)?;
-
         benef_info.total_allocation += diff;
-        benefs.set(beneficiary, benef_info);
+        benefs.set(key, benef_info);
         env.storage().instance().set(&BENEFS, &benefs);
 
+        event::emit_category_limit_updated(
+            &env,
+            campaign_id.clone(),
+            beneficiary,
+            category,
+            old_limit,
+            new_limit,
+            caller.clone(),
+        );
+        event::emit_campaign_audit_trail(&env, event::CATEGORY_LIMIT_UPDATED, campaign_id, caller);
+
         Ok(())
     }
 
-    /// Get all category limits for beneficiary
-    pub fn get_all_category_limits(env: Env, beneficiary: Address) -> Vec<CategoryLimit> {
-        let limits_key = Self::get_limits_key(&env, &beneficiary);
-        let cat_limits: Map<Symbol, CategoryLimit> = match env.storage().instance().get(&limits_key) {
+    /// Get all of a beneficiary's category limits in a campaign
+    pub fn get_all_category_limits(env: Env, beneficiary: Address, campaign_id: String) -> Vec<CategoryLimit> {
+        let limits_key = DataKey::Limits(campaign_id, beneficiary);
+        let cat_limits: Map<Category, CategoryLimit> = match env.storage().persistent().get(&limits_key) {
             Some(l) => l,
             None => return Vec::new(&env),
         };
@@ -554,10 +810,421 @@ impl BeneficiaryRegistry {
         result
     }
 
-    /// Helper function to generate limits storage key
-    fn get_limits_key(_env: &Env, _beneficiary: &Address) -> Symbol {
-        // Create unique key for each beneficiary's limits
-        // Using a combination approach for now
-        symbol_short!("LIM")
+    /// Discard the limits written under the old shared key and return the
+    /// beneficiaries they belonged to for review (operator or admin)
+    ///
+    /// Before limits were stored per beneficiary, every approval overwrote a
+    /// single shared map, so it holds the limits of the last beneficiary
+    /// approved and spending summed across all of them; no beneficiary's own
+    /// limits can be recovered from it. Each approved beneficiary without
+    /// limits of their own goes back to pending, to be approved again with
+    /// their limits, and the shared entry is removed. Returns the number of
+    /// beneficiaries returned for review.
+    pub fn migrate_legacy_limits(env: Env, caller: Address) -> Result<u32, Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        Self::rekey_legacy_beneficiaries(&env);
+        let migrated = Self::discard_legacy_limits(&env).ok_or(Error::NothingToMigrate)?;
+        for info in migrated.iter() {
+            event::emit_limits_migrated(&env, info.campaign_id.clone(), info.address, caller.clone());
+            event::emit_campaign_audit_trail(&env, event::LIMITS_MIGRATED, info.campaign_id, caller.clone());
+        }
+
+        Ok(migrated.len())
+    }
+}
+
+impl BeneficiaryRegistry {
+    /// Rewrite entries stored under schema `from` in the layout of `from + 1`
+    ///
+    /// Version 2 keeps registrations and category limits per campaign and
+    /// beneficiary. Version 1 registrations are rekeyed by the campaign
+    /// they name, and its shared limits are discarded as
+    /// `migrate_legacy_limits` does.
+    fn migrate_step(env: &Env, from: u32) {
+        if from == 1 {
+            Self::rekey_legacy_beneficiaries(env);
+            Self::discard_legacy_limits(env);
+        }
+    }
+
+    /// Move registrations keyed by address alone under their campaign
+    ///
+    /// Does nothing once the legacy entry is gone.
+    fn rekey_legacy_beneficiaries(env: &Env) {
+        let legacy: Map<Address, BeneficiaryInfo> = match env.storage().instance().get(&LEGACY_BENEFS) {
+            Some(legacy) => legacy,
+            None => return,
+        };
+
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
+            .instance()
+            .get(&BENEFS)
+            .unwrap_or(Map::new(env));
+        for (address, info) in legacy.iter() {
+            benefs.set((info.campaign_id.clone(), address), info);
+        }
+        env.storage().instance().set(&BENEFS, &benefs);
+        env.storage().instance().remove(&LEGACY_BENEFS);
+    }
+
+    /// Require the stored admin's authorization
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Require the caller's authorization and one of `roles`
+    fn require_role(env: &Env, caller: &Address, roles: &[Role]) -> Result<(), Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        rbac::require_role(env, &admin, caller, roles)
+    }
+
+    /// Require the caller's authorization as the NGO running `campaign_id`
+    ///
+    /// Field agents and the admin may act on any campaign. Campaign owners
+    /// are looked up in the configured ReliefVault.
+    fn require_campaign_ngo(env: &Env, caller: &Address, campaign_id: &String) -> Result<(), Error> {
+        caller.require_auth();
+
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        if rbac::has_role(env, &admin, Role::FieldAgent, caller) {
+            return Ok(());
+        }
+
+        let vault: Address = env.storage()
+            .instance()
+            .get(&VAULT)
+            .ok_or(Error::Unauthorized)?;
+        match VaultClient::new(env, &vault).get_campaign_ngo(campaign_id) {
+            Some(ngo) if ngo == *caller => Ok(()),
+            _ => Err(Error::Unauthorized),
+        }
+    }
+
+    /// Remove the shared legacy limits and return every approved
+    /// beneficiary without limits of their own to pending
+    ///
+    /// Returns the beneficiaries returned to pending, or `None` if there is
+    /// no legacy entry.
+    fn discard_legacy_limits(env: &Env) -> Option<Vec<BeneficiaryInfo>> {
+        if !env.storage().instance().has(&LEGACY_LIMITS) {
+            return None;
+        }
+        env.storage().instance().remove(&LEGACY_LIMITS);
+
+        let mut benefs: Map<(String, Address), BeneficiaryInfo> = env.storage()
+            .instance()
+            .get(&BENEFS)
+            .unwrap_or(Map::new(env));
+        let mut pending: Map<String, Vec<Address>> = env.storage()
+            .instance()
+            .get(&PENDING)
+            .unwrap_or(Map::new(env));
+        let mut migrated: Vec<BeneficiaryInfo> = Vec::new(env);
+        for ((campaign_id, address), mut info) in benefs.iter() {
+            let key = (campaign_id.clone(), address.clone());
+            if info.status != BeneficiaryStatus::Approved
+                || env.storage().persistent().has(&DataKey::Limits(campaign_id, address.clone()))
+            {
+                continue;
+            }
+            info.status = BeneficiaryStatus::Pending;
+            info.approved_at = 0;
+            info.total_allocation = 0;
+            benefs.set(key, info.clone());
+
+            let mut campaign_pending = pending.get(info.campaign_id.clone()).unwrap_or(Vec::new(env));
+            campaign_pending.push_back(address);
+            pending.set(info.campaign_id.clone(), campaign_pending);
+            migrated.push_back(info);
+        }
+        env.storage().instance().set(&BENEFS, &benefs);
+        env.storage().instance().set(&PENDING, &pending);
+
+        Some(migrated)
+    }
+
+    /// A beneficiary's limit for a category in a campaign, if the name is valid and limited
+    fn category_limit(
+        env: &Env,
+        beneficiary: &Address,
+        campaign_id: &String,
+        category: &Category,
+    ) -> Option<CategoryLimit> {
+        let category = category.normalize(env).ok()?;
+        let cat_limits: Map<Category, CategoryLimit> = env.storage()
+            .persistent()
+            .get(&DataKey::Limits(campaign_id.clone(), beneficiary.clone()))?;
+        cat_limits.get(category)
+    }
+
+    /// Client for the configured CategoryRegistry
+    fn category_registry(env: &Env) -> Result<CategoryRegistryClient<'_>, Error> {
+        let address: Address = env.storage()
+            .instance()
+            .get(&CATEGORIES)
+            .ok_or(Error::CategoryRegistryNotSet)?;
+        Ok(CategoryRegistryClient::new(env, &address))
+    }
+
+    /// A category followed by its ancestors in the taxonomy
+    fn category_path(env: &Env, category: &Category) -> Result<Vec<Category>, Error> {
+        let path = Self::category_registry(env)?.get_path(category);
+        if path.is_empty() {
+            return Err(Error::InvalidCategory);
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::testutils::{MockCategories, MockCategoriesClient, MockVault, MockVaultClient};
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup(env: &Env) -> (BeneficiaryRegistryClient<'_>, MockCategoriesClient<'_>) {
+        env.mock_all_auths();
+
+        let registry_id = env.register_contract(None, BeneficiaryRegistry);
+        let registry = BeneficiaryRegistryClient::new(env, &registry_id);
+        registry.initialize(&Address::generate(env));
+        let categories = MockCategoriesClient::new(env, &env.register_contract(None, MockCategories));
+        registry.set_category_registry(&categories.address);
+        (registry, categories)
+    }
+
+    #[test]
+    fn test_update_spending_gated_to_vault() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let ngo = Address::generate(&env);
+        registry.grant_role(&Role::FieldAgent, &ngo);
+        let beneficiary = Address::generate(&env);
+        let vault = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let campaign_id = String::from_str(&env, "flood");
+        registry.register_for_campaign(&beneficiary, &campaign_id, &String::from_str(&env, "doc"));
+        registry.approve_beneficiary(&ngo, &beneficiary, &campaign_id, &Vec::from_array(&env, [food.clone()]), &Vec::from_array(&env, [500i128]));
+
+        assert_eq!(
+            registry.try_update_spending(&beneficiary, &campaign_id, &food, &100),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        registry.set_vault(&vault);
+        registry.update_spending(&beneficiary, &campaign_id, &food, &100);
+        let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(signers, std::vec![vault]);
+        assert_eq!(registry.get_category_spent(&beneficiary, &campaign_id, &food), 100);
+    }
+
+    #[test]
+    fn test_limits_stored_per_beneficiary() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let ngo = Address::generate(&env);
+        registry.grant_role(&Role::FieldAgent, &ngo);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let campaign_id = String::from_str(&env, "flood");
+        let doc = String::from_str(&env, "doc");
+        let categories = Vec::from_array(&env, [food.clone()]);
+
+        registry.register_for_campaign(&first, &campaign_id, &doc);
+        registry.register_for_campaign(&second, &campaign_id, &doc);
+        registry.approve_beneficiary(&ngo, &first, &campaign_id, &categories, &Vec::from_array(&env, [300i128]));
+        registry.approve_beneficiary(&ngo, &second, &campaign_id, &categories, &Vec::from_array(&env, [800i128]));
+
+        assert_eq!(registry.get_category_limit(&first, &campaign_id, &food), 300);
+        assert_eq!(registry.get_category_limit(&second, &campaign_id, &food), 800);
+    }
+
+    /// Category limit as stored under schema version 1, named by a symbol
+    #[derive(Clone)]
+    #[contracttype]
+    struct LegacyCategoryLimit {
+        category: Symbol,
+        limit: i128,
+        spent: i128,
+    }
+
+    /// Store registrations as version 1 did, keyed by address alone, and
+    /// approve `approved` writing limits under the shared key
+    fn write_legacy_limits(env: &Env, registry: &BeneficiaryRegistryClient, approved: &[&Address]) {
+        let mut legacy: Map<Symbol, LegacyCategoryLimit> = Map::new(env);
+        for (name, limit) in [("Food", 400), ("medicine", 100)] {
+            let category = Symbol::new(env, name);
+            legacy.set(category.clone(), LegacyCategoryLimit { category, limit, spent: 50 });
+        }
+        env.as_contract(&registry.address, || {
+            env.storage().instance().set(&LEGACY_LIMITS, &legacy);
+            let benefs: Map<(String, Address), BeneficiaryInfo> = env.storage().instance().get(&BENEFS).unwrap();
+            let mut pending: Map<String, Vec<Address>> = env.storage().instance().get(&PENDING).unwrap();
+            let mut legacy_benefs: Map<Address, BeneficiaryInfo> = Map::new(env);
+            for mut info in benefs.values().iter() {
+                if approved.contains(&&info.address) {
+                    info.status = BeneficiaryStatus::Approved;
+                    info.total_allocation = 400;
+                    let mut campaign_pending = pending.get(info.campaign_id.clone()).unwrap();
+                    campaign_pending.remove(campaign_pending.first_index_of(&info.address).unwrap());
+                    pending.set(info.campaign_id.clone(), campaign_pending);
+                }
+                legacy_benefs.set(info.address.clone(), info);
+            }
+            env.storage().instance().set(&LEGACY_BENEFS, &legacy_benefs);
+            env.storage().instance().remove(&BENEFS);
+            env.storage().instance().set(&PENDING, &pending);
+        });
+    }
+
+    #[test]
+    fn test_migrate_legacy_limits() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let pending = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let campaign_id = String::from_str(&env, "flood");
+        let doc = String::from_str(&env, "doc");
+        for beneficiary in [&first, &second, &pending] {
+            registry.register_for_campaign(beneficiary, &campaign_id, &doc);
+        }
+
+        write_legacy_limits(&env, &registry, &[&first, &second]);
+
+        // The shared limits belong to nobody in particular, so nobody gets them
+        assert_eq!(registry.migrate_legacy_limits(&admin), 2);
+        for beneficiary in [&first, &second, &pending] {
+            assert_eq!(registry.get_category_limit(beneficiary, &campaign_id, &food), 0);
+            assert!(registry.get_application_status(beneficiary, &campaign_id).unwrap().status == BeneficiaryStatus::Pending);
+        }
+        assert_eq!(registry.get_pending_applications(&campaign_id).len(), 3);
+        assert_eq!(
+            registry.try_migrate_legacy_limits(&admin),
+            Err(Ok(Error::NothingToMigrate))
+        );
+
+        // Each is approved again with their own limits
+        registry.approve_beneficiary(&admin, &first, &campaign_id, &Vec::from_array(&env, [food.clone()]), &Vec::from_array(&env, [150i128]));
+        assert_eq!(registry.get_category_limit(&first, &campaign_id, &food), 150);
+    }
+
+    #[test]
+    fn test_migrate_discards_shared_limits() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let beneficiary = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let campaign_id = String::from_str(&env, "flood");
+        registry.register_for_campaign(&beneficiary, &campaign_id, &String::from_str(&env, "doc"));
+        write_legacy_limits(&env, &registry, &[&beneficiary]);
+        env.as_contract(&registry.address, || upgrade::set_version(&env, 1));
+
+        // Registrations are rekeyed by their campaign
+        assert_eq!(registry.migrate(), 2);
+        assert_eq!(registry.get_category_limit(&beneficiary, &campaign_id, &food), 0);
+        assert!(!registry.is_approved(&beneficiary));
+        assert_eq!(registry.get_pending_applications(&campaign_id).len(), 1);
+        assert_eq!(
+            registry.try_register_for_campaign(&beneficiary, &campaign_id, &String::from_str(&env, "doc")),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+        assert_eq!(
+            registry.try_migrate_legacy_limits(&admin),
+            Err(Ok(Error::NothingToMigrate))
+        );
+    }
+
+    #[test]
+    fn test_parent_limit_covers_children() {
+        let env = Env::default();
+        let (registry, categories) = setup(&env);
+        let ngo = Address::generate(&env);
+        registry.grant_role(&Role::FieldAgent, &ngo);
+        let beneficiary = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let formula = Category::new(&env, "infant_formula").unwrap();
+        let retired = Category::new(&env, "vouchers").unwrap();
+        categories.set_parent(&formula, &food);
+        categories.retire(&retired);
+        let campaign_id = String::from_str(&env, "flood");
+        registry.register_for_campaign(&beneficiary, &campaign_id, &String::from_str(&env, "doc"));
+
+        assert_eq!(
+            registry.try_approve_beneficiary(&ngo, &beneficiary, &campaign_id, &Vec::from_array(&env, [retired]), &Vec::from_array(&env, [100i128])),
+            Err(Ok(Error::InvalidCategory))
+        );
+        registry.approve_beneficiary(&ngo, &beneficiary, &campaign_id, &Vec::from_array(&env, [food.clone()]), &Vec::from_array(&env, [300i128]));
+        registry.set_vault(&Address::generate(&env));
+
+        registry.update_spending(&beneficiary, &campaign_id, &formula, &200);
+        assert_eq!(registry.get_category_spent(&beneficiary, &campaign_id, &food), 200);
+        assert_eq!(registry.get_category_balance(&beneficiary, &campaign_id, &food), 100);
+
+        // The vault enforces its own limits, so recording never fails on them
+        registry.update_spending(&beneficiary, &campaign_id, &formula, &150);
+        assert_eq!(registry.get_category_spent(&beneficiary, &campaign_id, &food), 350);
+    }
+
+    #[test]
+    fn test_only_campaign_ngo_reviews_beneficiaries() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let vault = MockVaultClient::new(&env, &env.register_contract(None, MockVault));
+        registry.set_vault(&vault.address);
+        let owner = Address::generate(&env);
+        let other_ngo = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let campaign_id = String::from_str(&env, "flood");
+        let categories = Vec::from_array(&env, [food.clone()]);
+        let limits = Vec::from_array(&env, [500i128]);
+        vault.set_campaign_ngo(&campaign_id, &owner);
+        registry.register_for_campaign(&beneficiary, &campaign_id, &String::from_str(&env, "doc"));
+
+        assert_eq!(
+            registry.try_approve_beneficiary(&other_ngo, &beneficiary, &campaign_id, &categories, &limits),
+            Err(Ok(Error::Unauthorized))
+        );
+        registry.approve_beneficiary(&owner, &beneficiary, &campaign_id, &categories, &limits);
+        assert!(registry.is_approved_for_campaign(&beneficiary, &campaign_id));
+        assert!(!registry.is_approved_for_campaign(&beneficiary, &String::from_str(&env, "drought")));
+        assert_eq!(
+            registry.try_update_category_limit(&other_ngo, &beneficiary, &campaign_id, &food, &900),
+            Err(Ok(Error::Unauthorized))
+        );
+        registry.update_category_limit(&owner, &beneficiary, &campaign_id, &food, &700);
+        assert_eq!(registry.get_category_limit(&beneficiary, &campaign_id, &food), 700);
+        assert_eq!(
+            registry.try_revoke_beneficiary(&other_ngo, &beneficiary, &campaign_id),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // A field agent may act for any campaign
+        registry.grant_role(&Role::FieldAgent, &other_ngo);
+        registry.revoke_beneficiary(&other_ngo, &beneficiary, &campaign_id);
+        assert!(!registry.is_approved(&beneficiary));
     }
 }
@@ -0,0 +1,582 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, symbol_short, Vec};
+use crate::admin::{self, PendingAdmin};
+use crate::error::Error;
+use crate::event;
+use crate::pause;
+use crate::rbac;
+use crate::ttl::{self, TtlConfig};
+use crate::types::{Category, Operation, ParentCategory, PauseScope, Role};
+use crate::upgrade;
+
+// Storage keys (max 9 chars for symbol_short!)
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const CATS: Symbol = symbol_short!("CATS");
+const INIT: Symbol = symbol_short!("INIT");
+
+/// Storage layout version written by this code
+const SCHEMA_VERSION: u32 = 2;
+
+/// Per-category storage keys
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// Definition of one category
+    Category(Category),
+    /// Direct children of one category
+    Children(Category),
+}
+
+/// Category definition
+///
+/// `parent` is fixed when the category is defined, so the hierarchy
+/// never changes under existing limits.
+#[derive(Clone)]
+#[contracttype]
+pub struct CategoryInfo {
+    pub category: Category,
+    pub display_name: String,
+    pub parent: ParentCategory,
+    pub active: bool,
+    pub created_at: u64,
+}
+
+/// Category Registry Contract
+/// Admin-defined spending categories shared by the vault and registries
+#[contract]
+pub struct CategoryRegistry;
+
+#[contractimpl]
+impl CategoryRegistry {
+    /// Initialize the category registry
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        // Check if already initialized
+        if env.storage().instance().has(&INIT) || upgrade::has_version(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        // Require admin authentication
+        admin.require_auth();
+
+        // Store admin
+        env.storage().instance().set(&ADMIN, &admin);
+
+        // Initialize empty category index
+        let categories: Vec<Category> = Vec::new(&env);
+        env.storage().instance().set(&CATS, &categories);
+
+        // Mark as initialized
+        env.storage().instance().set(&INIT, &true);
+        upgrade::set_version(&env, SCHEMA_VERSION);
+        ttl::extend_instance(&env);
+
+        event::emit_config_updated(&env, symbol_short!("admin"), admin.clone(), admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
+
+        Ok(())
+    }
+
+    /// Define a new category, optionally under an existing parent (operator or admin)
+    pub fn define_category(
+        env: Env,
+        caller: Address,
+        category: Category,
+        display_name: String,
+        parent: Option<Category>,
+    ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        let category = category.normalize(&env)?;
+        let parent = match parent {
+            Some(parent) => Some(parent.normalize(&env)?),
+            None => None,
+        };
+        let key = DataKey::Category(category.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::CategoryExists);
+        }
+
+        // Parent must already be defined
+        if let Some(parent) = parent.clone() {
+            if !env.storage().persistent().has(&DataKey::Category(parent.clone())) {
+                return Err(Error::InvalidCategory);
+            }
+            let children_key = DataKey::Children(parent);
+            let mut children: Vec<Category> = env.storage()
+                .persistent()
+                .get(&children_key)
+                .unwrap_or(Vec::new(&env));
+            children.push_back(category.clone());
+            env.storage().persistent().set(&children_key, &children);
+            ttl::extend_persistent(&env, &children_key);
+        }
+
+        let info = CategoryInfo {
+            category: category.clone(),
+            display_name: display_name.clone(),
+            parent: parent.clone().into(),
+            active: true,
+            created_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &info);
+        ttl::extend_persistent(&env, &key);
+
+        let mut categories: Vec<Category> = env.storage()
+            .instance()
+            .get(&CATS)
+            .unwrap_or(Vec::new(&env));
+        categories.push_back(category.clone());
+        env.storage().instance().set(&CATS, &categories);
+
+        // Emit definition event
+        event::emit_category_defined(&env, category, parent, display_name, caller.clone());
+        event::emit_audit_trail(&env, event::CATEGORY_DEFINED, caller);
+
+        Ok(())
+    }
+
+    /// Rename a category (operator or admin)
+    pub fn set_display_name(env: Env, caller: Address, category: Category, display_name: String) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        let category = category.normalize(&env)?;
+        let key = DataKey::Category(category.clone());
+        let mut info: CategoryInfo = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::InvalidCategory)?;
+
+        info.display_name = display_name;
+        env.storage().persistent().set(&key, &info);
+        ttl::extend_persistent(&env, &key);
+
+        event::emit_category_updated(&env, category, info.display_name, info.active, caller.clone());
+        event::emit_audit_trail(&env, event::CATEGORY_UPDATED, caller);
+
+        Ok(())
+    }
+
+    /// Activate or deactivate a category (operator or admin)
+    ///
+    /// Deactivating a category also deactivates its children. Existing
+    /// limits keep working; only new approvals and allocations are refused.
+    pub fn set_active(env: Env, caller: Address, category: Category, active: bool) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        let category = category.normalize(&env)?;
+        let key = DataKey::Category(category.clone());
+        let mut info: CategoryInfo = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::InvalidCategory)?;
+
+        info.active = active;
+        env.storage().persistent().set(&key, &info);
+        ttl::extend_persistent(&env, &key);
+
+        event::emit_category_updated(&env, category, info.display_name, active, caller.clone());
+        event::emit_audit_trail(&env, event::CATEGORY_UPDATED, caller);
+
+        Ok(())
+    }
+
+    /// Pause every state-changing operation, or only one (operator, auditor or admin)
+    pub fn pause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator or auditor
+        Self::require_role(&env, &caller, &[Role::Operator, Role::Auditor])?;
+
+        pause::set_paused(&env, scope, true, caller);
+        Ok(())
+    }
+
+    /// Lift a pause set for the same scope (operator or admin)
+    ///
+    /// Unpausing one operation leaves a contract-wide pause in place.
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        pause::set_paused(&env, scope, false, caller);
+        Ok(())
+    }
+
+    /// Check whether an operation may not run because of a pause
+    ///
+    /// `All` reports only a contract-wide pause.
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        pause::is_paused(&env, scope)
+    }
+
+    /// Propose a new admin, who takes over once they accept (admin only)
+    ///
+    /// `expires_at` is the ledger timestamp after which the proposal can no
+    /// longer be accepted; 0 means it never lapses. A new proposal replaces
+    /// the previous one.
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: u64) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        admin::propose(&env, admin, new_admin, expires_at)
+    }
+
+    /// Accept a pending admin proposal (proposed admin only)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let new_admin = admin::accept(&env)?;
+        env.storage().instance().set(&ADMIN, &new_admin);
+        Ok(())
+    }
+
+    /// Get the current admin
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ADMIN)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<PendingAdmin> {
+        admin::get_pending(&env)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::grant(&env, role, account, admin)
+    }
+
+    /// Revoke a role from an account (admin only)
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::revoke(&env, role, account, admin)
+    }
+
+    /// Check whether an account holds a role; the admin holds every role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        match env.storage().instance().get::<_, Address>(&ADMIN) {
+            Some(admin) => rbac::has_role(&env, &admin, role, &account),
+            None => false,
+        }
+    }
+
+    /// Replace this contract's code with an uploaded wasm (admin only)
+    ///
+    /// Storage is kept; call `migrate` afterwards to convert it if the new
+    /// code changed its layout.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::upgrade(&env, new_wasm_hash, admin);
+        Ok(())
+    }
+
+    /// Schema version of the data in storage
+    pub fn version(env: Env) -> u32 {
+        upgrade::stored_version(&env)
+    }
+
+    /// Convert stored data to this code's schema version (admin only)
+    ///
+    /// Returns the version now in storage.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::migrate(&env, SCHEMA_VERSION, Self::migrate_step, admin)
+    }
+
+    /// Set how far and how often storage lifetimes are extended (admin only)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        ttl::set_config(&env, config, admin)
+    }
+
+    /// Get the storage lifetime settings
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl::get_config(&env)
+    }
+
+    /// Get a category definition
+    pub fn get_category(env: Env, category: Category) -> Option<CategoryInfo> {
+        let category = category.normalize(&env).ok()?;
+        env.storage().persistent().get(&DataKey::Category(category))
+    }
+
+    /// Get the direct children of a category
+    pub fn get_children(env: Env, category: Category) -> Vec<Category> {
+        let category = match category.normalize(&env) {
+            Ok(category) => category,
+            Err(_) => return Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .get(&DataKey::Children(category))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get all category definitions
+    pub fn get_all_categories(env: Env) -> Vec<CategoryInfo> {
+        let categories: Vec<Category> = env.storage()
+            .instance()
+            .get(&CATS)
+            .unwrap_or(Vec::new(&env));
+
+        let mut result: Vec<CategoryInfo> = Vec::new(&env);
+        for category in categories.iter() {
+            if let Some(info) = Self::get_category(env.clone(), category) {
+                result.push_back(info);
+            }
+        }
+        result
+    }
+
+    /// Check a category and all its ancestors are defined and active
+    pub fn is_active(env: Env, category: Category) -> bool {
+        ttl::extend_instance(&env);
+
+        let mut current = category.normalize(&env).ok();
+        if current.is_none() {
+            return false;
+        }
+        while let Some(category) = current {
+            match Self::load_category(&env, category) {
+                Some(info) if info.active => current = info.parent.category(),
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Get a category followed by its ancestors, nearest first
+    ///
+    /// Empty if the category is not defined. A limit on any category in
+    /// the path covers spending in the first one.
+    pub fn get_path(env: Env, category: Category) -> Vec<Category> {
+        ttl::extend_instance(&env);
+
+        let mut path: Vec<Category> = Vec::new(&env);
+        let mut current = category.normalize(&env).ok();
+        while let Some(category) = current {
+            match Self::load_category(&env, category.clone()) {
+                Some(info) => {
+                    path.push_back(category);
+                    current = info.parent.category();
+                }
+                None => return Vec::new(&env),
+            }
+        }
+        path
+    }
+
+    /// Extend a category's definition and child list (anyone)
+    pub fn bump_category(env: Env, category: Category) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let category = category.normalize(&env)?;
+        if !ttl::extend_persistent(&env, &DataKey::Category(category.clone())) {
+            return Err(Error::InvalidCategory);
+        }
+        ttl::extend_persistent(&env, &DataKey::Children(category));
+        Ok(())
+    }
+}
+
+impl CategoryRegistry {
+    /// Rewrite entries stored under schema `from` in the layout of `from + 1`
+    ///
+    /// The registry was introduced with schema version 2, so no storage of
+    /// an older layout exists.
+    fn migrate_step(_env: &Env, _from: u32) {}
+
+    /// Require the caller's authorization and one of `roles`
+    fn require_role(env: &Env, caller: &Address, roles: &[Role]) -> Result<(), Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        rbac::require_role(env, &admin, caller, roles)
+    }
+
+    /// Read a category definition and extend it, since it is still in use
+    fn load_category(env: &Env, category: Category) -> Option<CategoryInfo> {
+        let key = DataKey::Category(category);
+        let info: CategoryInfo = env.storage().persistent().get(&key)?;
+        ttl::extend_persistent(env, &key);
+        Some(info)
+    }
+
+    /// Require the stored admin's authorization
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup(env: &Env) -> CategoryRegistryClient<'_> {
+        env.mock_all_auths();
+
+        let registry_id = env.register_contract(None, CategoryRegistry);
+        let registry = CategoryRegistryClient::new(env, &registry_id);
+        registry.initialize(&Address::generate(env));
+        registry
+    }
+
+    #[test]
+    fn test_hierarchy_and_paths() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let food = Category::new(&env, "food").unwrap();
+        let formula = Category::new(&env, "infant_formula").unwrap();
+
+        registry.define_category(&admin, &food, &String::from_str(&env, "Food"), &None);
+        registry.define_category(&admin, &formula, &String::from_str(&env, "Infant formula"), &Some(food.clone()));
+
+        assert_eq!(registry.get_path(&formula), Vec::from_array(&env, [formula.clone(), food.clone()]));
+        assert_eq!(registry.get_children(&food), Vec::from_array(&env, [formula.clone()]));
+        assert_eq!(registry.get_path(&Category::new(&env, "groceries").unwrap()).len(), 0);
+        assert_eq!(
+            registry.try_define_category(&admin, &food, &String::from_str(&env, "Food"), &None),
+            Err(Ok(Error::CategoryExists))
+        );
+        assert_eq!(
+            registry.try_define_category(&admin, &Category::new(&env, "rice").unwrap(), &String::from_str(&env, "Rice"), &Some(Category::new(&env, "grain").unwrap())),
+            Err(Ok(Error::InvalidCategory))
+        );
+    }
+
+    #[test]
+    fn test_category_names_are_normalized() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let food = Category::new(&env, "food").unwrap();
+
+        assert_eq!(Category::new(&env, "Food"), Ok(food.clone()));
+        assert_eq!(Category::new(&env, "food aid"), Err(Error::InvalidCategory));
+
+        registry.define_category(&admin, &Category::unchecked(&env, "Food"), &String::from_str(&env, "Food"), &None);
+        assert_eq!(registry.get_category(&food).unwrap().category, food);
+        assert!(registry.is_active(&Category::unchecked(&env, "FOOD")));
+        assert_eq!(
+            registry.try_define_category(&admin, &Category::unchecked(&env, "FOOD"), &String::from_str(&env, "Food"), &None),
+            Err(Ok(Error::CategoryExists))
+        );
+        for name in ["", "food-aid", "food aid", "a_category_name_longer_than_32_bytes"] {
+            assert_eq!(
+                registry.try_define_category(&admin, &Category::unchecked(&env, name), &String::from_str(&env, "Bad"), &None),
+                Err(Ok(Error::InvalidCategory))
+            );
+            assert!(!registry.is_active(&Category::unchecked(&env, name)));
+        }
+    }
+
+    #[test]
+    fn test_deactivating_parent_deactivates_children() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let food = Category::new(&env, "food").unwrap();
+        let formula = Category::new(&env, "infant_formula").unwrap();
+        registry.define_category(&admin, &food, &String::from_str(&env, "Food"), &None);
+        registry.define_category(&admin, &formula, &String::from_str(&env, "Infant formula"), &Some(food.clone()));
+
+        registry.set_active(&admin, &food, &false);
+        assert!(!registry.is_active(&food));
+        assert!(!registry.is_active(&formula));
+        assert_eq!(registry.get_path(&formula).len(), 2);
+
+        registry.set_active(&admin, &food, &true);
+        assert!(registry.is_active(&formula));
+    }
+
+    #[test]
+    fn test_schema_version_and_migrate() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let food = Category::new(&env, "food").unwrap();
+        registry.define_category(&admin, &food, &String::from_str(&env, "Food"), &None);
+
+        assert_eq!(registry.version(), SCHEMA_VERSION);
+        assert_eq!(registry.migrate(), SCHEMA_VERSION);
+        assert!(registry.is_active(&food));
+
+        // Storage written by newer code cannot be migrated down
+        env.as_contract(&registry.address, || upgrade::set_version(&env, SCHEMA_VERSION + 1));
+        assert_eq!(registry.try_migrate(), Err(Ok(Error::UnsupportedSchemaVersion)));
+    }
+
+    #[test]
+    fn test_lookups_extend_categories() {
+        use soroban_sdk::testutils::storage::Persistent as _;
+        use soroban_sdk::testutils::Ledger;
+
+        let env = Env::default();
+        let registry = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let food = Category::new(&env, "food").unwrap();
+        let formula = Category::new(&env, "infant_formula").unwrap();
+        registry.define_category(&admin, &food, &String::from_str(&env, "Food"), &None);
+        registry.define_category(&admin, &formula, &String::from_str(&env, "Infant formula"), &Some(food.clone()));
+
+        // Keep the instance alive across the gaps below
+        let config = TtlConfig {
+            instance_threshold: 150 * ttl::DAY_IN_LEDGERS,
+            instance_extend_to: 200 * ttl::DAY_IN_LEDGERS,
+            ..TtlConfig::default_config()
+        };
+        registry.set_ttl_config(&config);
+        let extend_to = config.persistent_extend_to;
+        let ttl_of = |key: &DataKey| env.as_contract(&registry.address, || env.storage().persistent().get_ttl(key));
+        let food_key = DataKey::Category(food.clone());
+        let children_key = DataKey::Children(food.clone());
+        env.ledger().with_mut(|li| li.sequence_number += 95 * ttl::DAY_IN_LEDGERS);
+        assert_eq!(ttl_of(&food_key), 25 * ttl::DAY_IN_LEDGERS);
+
+        // Checking a child keeps its ancestors alive
+        assert!(registry.is_active(&formula));
+        assert_eq!(ttl_of(&food_key), extend_to);
+        assert_eq!(ttl_of(&children_key), 25 * ttl::DAY_IN_LEDGERS);
+
+        registry.bump_category(&food);
+        assert_eq!(ttl_of(&children_key), extend_to);
+        assert_eq!(registry.try_bump_category(&Category::new(&env, "fuel").unwrap()), Err(Ok(Error::InvalidCategory)));
+    }
+}
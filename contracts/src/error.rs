@@ -49,4 +49,82 @@ pub enum Error {
     
     /// Campaign already exists
     CampaignExists = 15,
+    
+    /// Campaign cannot move from its current status to the requested one
+    InvalidStatusTransition = 16,
+    
+    /// Campaign status does not allow this operation
+    CampaignNotActive = 17,
+    
+    /// Campaign is not closed or cancelled, so refunds are not open
+    RefundNotAvailable = 18,
+    
+    /// Donor has no refund left to claim
+    NothingToRefund = 19,
+    
+    /// Campaign deadlines are in the past or out of order
+    InvalidDeadline = 20,
+    
+    /// Campaign fundraising deadline has passed
+    FundraisingEnded = 21,
+    
+    /// Donation would take the campaign past its target
+    TargetReached = 22,
+    
+    /// Campaign spending deadline has passed
+    SpendingWindowClosed = 23,
+    
+    /// Authorization was already executed, cancelled or expired
+    AuthorizationNotPending = 24,
+    
+    /// Authorization is past its expiry time
+    AuthorizationExpired = 25,
+    
+    /// Authorization has not reached its expiry time yet
+    AuthorizationNotExpired = 26,
+    
+    /// Category is not defined in the taxonomy or is inactive
+    InvalidCategory = 27,
+    
+    /// Registration number is already used by another NGO
+    DuplicateRegistration = 28,
+    
+    /// Category is already defined in the taxonomy
+    CategoryExists = 29,
+    
+    /// No category registry has been configured
+    CategoryRegistryNotSet = 30,
+    
+    /// Contract, or this operation, is paused by the admin
+    ContractPaused = 31,
+    
+    /// Storage was written by a newer schema than this code understands
+    UnsupportedSchemaVersion = 32,
+    
+    /// No admin transfer has been proposed
+    NoPendingAdmin = 33,
+    
+    /// Proposed admin did not accept before the proposal expired
+    AdminProposalExpired = 34,
+    
+    /// Role cannot be granted or revoked directly
+    InvalidRole = 35,
+
+    /// TTL threshold is above its extension or the extension is out of range
+    InvalidTtlConfig = 36,
+
+    /// Address already has an NGO registration
+    NgoAlreadyRegistered = 37,
+
+    /// Registration number is empty or too long
+    InvalidRegistrationNumber = 38,
+
+    /// No data in an older layout is left to migrate
+    NothingToMigrate = 39,
+
+    /// Beneficiary or merchant registry did not record a payment
+    RegistryUpdateFailed = 40,
+
+    /// Contract setting, such as a registry address, has not been set
+    NotInitialized = 41,
 }
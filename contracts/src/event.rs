@@ -1,19 +1,61 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
+use crate::ttl::TtlConfig;
+use crate::types::{CampaignStatus, Category, ControlMode, ParentCategory, PauseScope, Role};
 
-/// Events emitted by Relifo contracts for audit trail
+// Events emitted by Relifo contracts for audit trail
+//
+// Topics are always (event_name, campaign_id, primary_address) so indexers
+// can filter without decoding bodies; events outside a campaign carry an
+// empty campaign id. The body is (EVENT_SCHEMA_VERSION, event struct).
+
+/// Version of the event body layout, bumped on any breaking change
+pub const EVENT_SCHEMA_VERSION: u32 = 2;
 
 // Event topic symbols (max 9 characters for symbol_short!)
 pub const NGO_REGISTERED: Symbol = symbol_short!("ngo_reg");
 pub const NGO_VERIFIED: Symbol = symbol_short!("ngo_ver");
+pub const NGO_REVOKED: Symbol = symbol_short!("ngo_rev");
+pub const NGO_SUSPENDED: Symbol = symbol_short!("ngo_susp");
+pub const NGO_CAMPAIGN_COUNTED: Symbol = symbol_short!("ngo_camp");
 pub const CAMPAIGN_CREATED: Symbol = symbol_short!("camp_crt");
+pub const CAMPAIGN_STATUS: Symbol = symbol_short!("camp_stat");
+pub const COSIGN_UPDATED: Symbol = symbol_short!("cosign");
 pub const DONATION_RECEIVED: Symbol = symbol_short!("donation");
-pub const BENEFICIARY_WHITELISTED: Symbol = symbol_short!("ben_wl");
+pub const REFUND_CLAIMED: Symbol = symbol_short!("refund");
+pub const BENEFICIARY_REGISTERED: Symbol = symbol_short!("ben_reg");
+pub const BENEFICIARY_APPROVED: Symbol = symbol_short!("ben_apr");
+pub const BENEFICIARY_REJECTED: Symbol = symbol_short!("ben_rej");
 pub const BENEFICIARY_REVOKED: Symbol = symbol_short!("ben_rev");
+pub const CATEGORY_LIMIT_UPDATED: Symbol = symbol_short!("limit_upd");
+pub const SPENDING_RECORDED: Symbol = symbol_short!("spend_rec");
+pub const LIMITS_MIGRATED: Symbol = symbol_short!("lim_migr");
 pub const FUNDS_ALLOCATED: Symbol = symbol_short!("fnd_alloc");
 pub const DIRECT_TRANSFER: Symbol = symbol_short!("direct");
 pub const SPENDING_AUTHORIZED: Symbol = symbol_short!("spnd_auth");
 pub const TRANSACTION_EXECUTED: Symbol = symbol_short!("tx_exec");
+pub const AUTH_CANCELLED: Symbol = symbol_short!("auth_cncl");
+pub const AUTH_EXPIRED: Symbol = symbol_short!("auth_exp");
 pub const MERCHANT_REGISTERED: Symbol = symbol_short!("merch_reg");
+pub const MERCHANT_APPLIED: Symbol = symbol_short!("merch_app");
+pub const MERCHANT_REJECTED: Symbol = symbol_short!("merch_rej");
+pub const MERCHANT_APPROVED: Symbol = symbol_short!("merch_apr");
+pub const MERCHANT_REVOKED: Symbol = symbol_short!("merch_rev");
+pub const MERCHANT_SUSPENDED: Symbol = symbol_short!("merch_sus");
+pub const MERCHANT_CATEGORY_ADDED: Symbol = symbol_short!("mcat_add");
+pub const MERCHANT_CATEGORY_REMOVED: Symbol = symbol_short!("mcat_rem");
+pub const PAYMENT_RECORDED: Symbol = symbol_short!("merch_pay");
+pub const CATEGORY_DEFINED: Symbol = symbol_short!("cat_def");
+pub const CATEGORY_UPDATED: Symbol = symbol_short!("cat_upd");
+pub const CONFIG_UPDATED: Symbol = symbol_short!("cfg_upd");
+pub const CONTRACT_PAUSED: Symbol = symbol_short!("paused");
+pub const CONTRACT_UNPAUSED: Symbol = symbol_short!("unpaused");
+pub const CONTRACT_UPGRADED: Symbol = symbol_short!("upgraded");
+pub const SCHEMA_MIGRATED: Symbol = symbol_short!("migrated");
+pub const ADMIN_PROPOSED: Symbol = symbol_short!("adm_prop");
+pub const ADMIN_CHANGED: Symbol = symbol_short!("adm_chg");
+pub const ROLE_GRANTED: Symbol = symbol_short!("role_grt");
+pub const ROLE_REVOKED: Symbol = symbol_short!("role_rev");
+pub const TTL_CONFIG_UPDATED: Symbol = symbol_short!("ttl_cfg");
 pub const AUDIT_TRAIL: Symbol = symbol_short!("audit");
 
 #[contracttype]
@@ -33,13 +75,56 @@ pub struct NGOVerifiedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NGORevokedEvent {
+    pub ngo_address: Address,
+    pub revoked_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NGOSuspendedEvent {
+    pub ngo_address: Address,
+    pub suspended_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NGOCampaignCountedEvent {
+    pub ngo_address: Address,
+    pub total_campaigns: u32,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CampaignCreatedEvent {
     pub campaign_id: String,
     pub ngo_address: Address,
     pub target_amount: i128,
-    pub control_mode: String,
+    pub control_mode: ControlMode,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampaignStatusChangedEvent {
+    pub campaign_id: String,
+    pub actor: Address,
+    pub old_status: CampaignStatus,
+    pub new_status: CampaignStatus,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CosignUpdatedEvent {
+    pub campaign_id: String,
+    pub ngo_address: Address,
+    pub required: bool,
     pub timestamp: u64,
 }
 
@@ -54,22 +139,82 @@ pub struct DonationReceivedEvent {
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct BeneficiaryWhitelistedEvent {
+pub struct RefundClaimedEvent {
+    pub campaign_id: String,
+    pub donor_address: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryRegisteredEvent {
     pub campaign_id: String,
     pub beneficiary_address: Address,
-    pub control_mode: String,
     pub timestamp: u64,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct BeneficiaryRevokedEvent {
+pub struct BeneficiaryApprovedEvent {
+    pub campaign_id: String,
+    pub beneficiary_address: Address,
+    pub approved_by: Address,
+    pub categories: Vec<Category>,
+    pub total_allocation: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryRejectedEvent {
     pub campaign_id: String,
     pub beneficiary_address: Address,
+    pub rejected_by: Address,
     pub reason: String,
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryRevokedEvent {
+    pub campaign_id: String,
+    pub beneficiary_address: Address,
+    pub revoked_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryLimitUpdatedEvent {
+    pub campaign_id: String,
+    pub beneficiary_address: Address,
+    pub category: Category,
+    pub old_limit: i128,
+    pub new_limit: i128,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingRecordedEvent {
+    pub campaign_id: String,
+    pub beneficiary_address: Address,
+    pub category: Category,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LimitsMigratedEvent {
+    pub campaign_id: String,
+    pub beneficiary_address: Address,
+    pub migrated_by: Address,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FundsAllocatedEvent {
@@ -91,48 +236,251 @@ pub struct DirectTransferEvent {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SpendingAuthorizedEvent {
+    pub auth_id: u64,
+    pub campaign_id: String,
     pub beneficiary_address: Address,
     pub merchant_address: Address,
-    pub category: Symbol,
+    pub category: Category,
     pub amount: i128,
+    pub expires_at: u64,
     pub timestamp: u64,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TransactionExecutedEvent {
+    pub auth_id: u64,
+    pub campaign_id: String,
     pub beneficiary_address: Address,
     pub merchant_address: Address,
-    pub category: Symbol,
+    pub category: Category,
     pub amount: i128,
     pub remaining_balance: i128,
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizationCancelledEvent {
+    pub auth_id: u64,
+    pub campaign_id: String,
+    pub beneficiary_address: Address,
+    pub cancelled_by: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizationExpiredEvent {
+    pub auth_id: u64,
+    pub campaign_id: String,
+    pub beneficiary_address: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MerchantRegisteredEvent {
     pub merchant_address: Address,
     pub name: String,
-    pub categories: String,
+    pub categories: Vec<Category>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantAppliedEvent {
+    pub merchant_address: Address,
+    pub name: String,
+    pub requested_categories: Vec<Category>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantRejectedEvent {
+    pub merchant_address: Address,
+    pub rejected_by: Address,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantApprovedEvent {
+    pub merchant_address: Address,
+    pub approved_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantRevokedEvent {
+    pub merchant_address: Address,
+    pub revoked_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantSuspendedEvent {
+    pub merchant_address: Address,
+    pub suspended_by: Address,
+    pub timestamp: u64,
+}
+
+/// Category added to or removed from a merchant's approvals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantCategoryEvent {
+    pub merchant_address: Address,
+    pub category: Category,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRecordedEvent {
+    pub merchant_address: Address,
+    pub amount: i128,
+    pub total_received: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryDefinedEvent {
+    pub category: Category,
+    pub parent: ParentCategory,
+    pub display_name: String,
+    pub defined_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryUpdatedEvent {
+    pub category: Category,
+    pub display_name: String,
+    pub active: bool,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+/// Contract setting pointed at a new address, such as the vault
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigUpdatedEvent {
+    pub setting: Symbol,
+    pub value: Address,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+/// Contract paused or unpaused, entirely or for one operation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseEvent {
+    pub scope: PauseScope,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractUpgradedEvent {
+    pub wasm_hash: BytesN<32>,
+    pub upgraded_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaMigratedEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub migrated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposedEvent {
+    pub current_admin: Address,
+    pub proposed_admin: Address,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminChangedEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+    pub timestamp: u64,
+}
+
+/// Role granted to or revoked from an account
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleEvent {
+    pub role: Role,
+    pub account: Address,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfigUpdatedEvent {
+    pub config: TtlConfig,
+    pub updated_by: Address,
     pub timestamp: u64,
 }
 
+/// Who performed a registry state change
+///
+/// Emitted next to the typed event; `event_type` is that event's name.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AuditTrailEvent {
     pub event_type: Symbol,
     pub campaign_id: String,
     pub actor: Address,
-    pub details: String,
     pub timestamp: u64,
 }
 
+/// Publish under the standard topics with a versioned body
+fn publish<T: IntoVal<Env, Val>>(
+    env: &Env,
+    event_name: Symbol,
+    campaign_id: String,
+    primary_address: Address,
+    event: T,
+) {
+    let event: Val = event.into_val(env);
+    env.events().publish(
+        (event_name, campaign_id, primary_address),
+        (EVENT_SCHEMA_VERSION, event),
+    );
+}
+
+/// Campaign id topic for events outside any campaign
+fn no_campaign(env: &Env) -> String {
+    String::from_str(env, "")
+}
+
 /// Helper functions to emit events
 pub fn emit_ngo_registered(env: &Env, ngo_address: Address, name: String, country: String) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (NGO_REGISTERED, ngo_address.clone()),
+    publish(
+        env,
+        NGO_REGISTERED,
+        no_campaign(env),
+        ngo_address.clone(),
         NGORegisteredEvent {
             ngo_address,
             name,
@@ -144,8 +492,11 @@ pub fn emit_ngo_registered(env: &Env, ngo_address: Address, name: String, countr
 
 pub fn emit_ngo_verified(env: &Env, ngo_address: Address, verified_by: Address) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (NGO_VERIFIED, ngo_address.clone()),
+    publish(
+        env,
+        NGO_VERIFIED,
+        no_campaign(env),
+        ngo_address.clone(),
         NGOVerifiedEvent {
             ngo_address,
             verified_by,
@@ -154,16 +505,64 @@ pub fn emit_ngo_verified(env: &Env, ngo_address: Address, verified_by: Address)
     );
 }
 
+pub fn emit_ngo_revoked(env: &Env, ngo_address: Address, revoked_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        NGO_REVOKED,
+        no_campaign(env),
+        ngo_address.clone(),
+        NGORevokedEvent {
+            ngo_address,
+            revoked_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_ngo_suspended(env: &Env, ngo_address: Address, suspended_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        NGO_SUSPENDED,
+        no_campaign(env),
+        ngo_address.clone(),
+        NGOSuspendedEvent {
+            ngo_address,
+            suspended_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_ngo_campaign_counted(env: &Env, ngo_address: Address, total_campaigns: u32) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        NGO_CAMPAIGN_COUNTED,
+        no_campaign(env),
+        ngo_address.clone(),
+        NGOCampaignCountedEvent {
+            ngo_address,
+            total_campaigns,
+            timestamp,
+        },
+    );
+}
+
 pub fn emit_campaign_created(
     env: &Env,
     campaign_id: String,
     ngo_address: Address,
     target_amount: i128,
-    control_mode: String,
+    control_mode: ControlMode,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (CAMPAIGN_CREATED, campaign_id.clone()),
+    publish(
+        env,
+        CAMPAIGN_CREATED,
+        campaign_id.clone(),
+        ngo_address.clone(),
         CampaignCreatedEvent {
             campaign_id,
             ngo_address,
@@ -174,69 +573,254 @@ pub fn emit_campaign_created(
     );
 }
 
-pub fn emit_donation_received(
+pub fn emit_campaign_status_changed(
     env: &Env,
     campaign_id: String,
-    donor_address: Address,
-    amount: i128,
+    actor: Address,
+    old_status: CampaignStatus,
+    new_status: CampaignStatus,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (DONATION_RECEIVED, campaign_id.clone()),
-        DonationReceivedEvent {
+    publish(
+        env,
+        CAMPAIGN_STATUS,
+        campaign_id.clone(),
+        actor.clone(),
+        CampaignStatusChangedEvent {
             campaign_id,
-            donor_address,
-            amount,
+            actor,
+            old_status,
+            new_status,
             timestamp,
         },
     );
 }
 
-pub fn emit_beneficiary_whitelisted(
-    env: &Env,
-    campaign_id: String,
-    beneficiary_address: Address,
-    control_mode: String,
-) {
+pub fn emit_cosign_updated(env: &Env, campaign_id: String, ngo_address: Address, required: bool) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (BENEFICIARY_WHITELISTED, beneficiary_address.clone()),
-        BeneficiaryWhitelistedEvent {
+    publish(
+        env,
+        COSIGN_UPDATED,
+        campaign_id.clone(),
+        ngo_address.clone(),
+        CosignUpdatedEvent {
             campaign_id,
-            beneficiary_address,
-            control_mode,
+            ngo_address,
+            required,
             timestamp,
         },
     );
 }
 
-pub fn emit_beneficiary_revoked(
+pub fn emit_donation_received(
     env: &Env,
     campaign_id: String,
-    beneficiary_address: Address,
-    reason: String,
+    donor_address: Address,
+    amount: i128,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (BENEFICIARY_REVOKED, beneficiary_address.clone()),
-        BeneficiaryRevokedEvent {
+    publish(
+        env,
+        DONATION_RECEIVED,
+        campaign_id.clone(),
+        donor_address.clone(),
+        DonationReceivedEvent {
             campaign_id,
-            beneficiary_address,
-            reason,
+            donor_address,
+            amount,
             timestamp,
         },
     );
 }
 
-pub fn emit_funds_allocated(
+pub fn emit_refund_claimed(
+    env: &Env,
+    campaign_id: String,
+    donor_address: Address,
+    amount: i128,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        REFUND_CLAIMED,
+        campaign_id.clone(),
+        donor_address.clone(),
+        RefundClaimedEvent {
+            campaign_id,
+            donor_address,
+            amount,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_beneficiary_registered(env: &Env, campaign_id: String, beneficiary_address: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        BENEFICIARY_REGISTERED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        BeneficiaryRegisteredEvent {
+            campaign_id,
+            beneficiary_address,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_beneficiary_approved(
+    env: &Env,
+    campaign_id: String,
+    beneficiary_address: Address,
+    approved_by: Address,
+    categories: Vec<Category>,
+    total_allocation: i128,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        BENEFICIARY_APPROVED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        BeneficiaryApprovedEvent {
+            campaign_id,
+            beneficiary_address,
+            approved_by,
+            categories,
+            total_allocation,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_beneficiary_rejected(
+    env: &Env,
+    campaign_id: String,
+    beneficiary_address: Address,
+    rejected_by: Address,
+    reason: String,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        BENEFICIARY_REJECTED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        BeneficiaryRejectedEvent {
+            campaign_id,
+            beneficiary_address,
+            rejected_by,
+            reason,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_beneficiary_revoked(
+    env: &Env,
+    campaign_id: String,
+    beneficiary_address: Address,
+    revoked_by: Address,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        BENEFICIARY_REVOKED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        BeneficiaryRevokedEvent {
+            campaign_id,
+            beneficiary_address,
+            revoked_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_category_limit_updated(
+    env: &Env,
+    campaign_id: String,
+    beneficiary_address: Address,
+    category: Category,
+    old_limit: i128,
+    new_limit: i128,
+    updated_by: Address,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        CATEGORY_LIMIT_UPDATED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        CategoryLimitUpdatedEvent {
+            campaign_id,
+            beneficiary_address,
+            category,
+            old_limit,
+            new_limit,
+            updated_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_spending_recorded(
     env: &Env,
     campaign_id: String,
     beneficiary_address: Address,
+    category: Category,
     amount: i128,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (FUNDS_ALLOCATED, beneficiary_address.clone()),
+    publish(
+        env,
+        SPENDING_RECORDED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        SpendingRecordedEvent {
+            campaign_id,
+            beneficiary_address,
+            category,
+            amount,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_limits_migrated(
+    env: &Env,
+    campaign_id: String,
+    beneficiary_address: Address,
+    migrated_by: Address,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        LIMITS_MIGRATED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        LimitsMigratedEvent {
+            campaign_id,
+            beneficiary_address,
+            migrated_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_funds_allocated(
+    env: &Env,
+    campaign_id: String,
+    beneficiary_address: Address,
+    amount: i128,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        FUNDS_ALLOCATED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
         FundsAllocatedEvent {
             campaign_id,
             beneficiary_address,
@@ -253,8 +837,11 @@ pub fn emit_direct_transfer(
     amount: i128,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (DIRECT_TRANSFER, beneficiary_address.clone()),
+    publish(
+        env,
+        DIRECT_TRANSFER,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
         DirectTransferEvent {
             campaign_id,
             beneficiary_address,
@@ -264,38 +851,56 @@ pub fn emit_direct_transfer(
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn emit_spending_authorized(
     env: &Env,
+    auth_id: u64,
+    campaign_id: String,
     beneficiary_address: Address,
     merchant_address: Address,
-    category: Symbol,
+    category: Category,
     amount: i128,
+    expires_at: u64,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (SPENDING_AUTHORIZED, beneficiary_address.clone()),
+    publish(
+        env,
+        SPENDING_AUTHORIZED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
         SpendingAuthorizedEvent {
+            auth_id,
+            campaign_id,
             beneficiary_address,
             merchant_address,
             category,
             amount,
+            expires_at,
             timestamp,
         },
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn emit_transaction_executed(
     env: &Env,
+    auth_id: u64,
+    campaign_id: String,
     beneficiary_address: Address,
     merchant_address: Address,
-    category: Symbol,
+    category: Category,
     amount: i128,
     remaining_balance: i128,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (TRANSACTION_EXECUTED, beneficiary_address.clone()),
+    publish(
+        env,
+        TRANSACTION_EXECUTED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
         TransactionExecutedEvent {
+            auth_id,
+            campaign_id,
             beneficiary_address,
             merchant_address,
             category,
@@ -306,15 +911,66 @@ pub fn emit_transaction_executed(
     );
 }
 
+pub fn emit_authorization_cancelled(
+    env: &Env,
+    auth_id: u64,
+    campaign_id: String,
+    beneficiary_address: Address,
+    cancelled_by: Address,
+    amount: i128,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        AUTH_CANCELLED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        AuthorizationCancelledEvent {
+            auth_id,
+            campaign_id,
+            beneficiary_address,
+            cancelled_by,
+            amount,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_authorization_expired(
+    env: &Env,
+    auth_id: u64,
+    campaign_id: String,
+    beneficiary_address: Address,
+    amount: i128,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        AUTH_EXPIRED,
+        campaign_id.clone(),
+        beneficiary_address.clone(),
+        AuthorizationExpiredEvent {
+            auth_id,
+            campaign_id,
+            beneficiary_address,
+            amount,
+            timestamp,
+        },
+    );
+}
+
 pub fn emit_merchant_registered(
     env: &Env,
     merchant_address: Address,
     name: String,
-    categories: String,
+    categories: Vec<Category>,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (MERCHANT_REGISTERED, merchant_address.clone()),
+    publish(
+        env,
+        MERCHANT_REGISTERED,
+        no_campaign(env),
+        merchant_address.clone(),
         MerchantRegisteredEvent {
             merchant_address,
             name,
@@ -324,23 +980,383 @@ pub fn emit_merchant_registered(
     );
 }
 
-pub fn emit_audit_trail(
+pub fn emit_merchant_applied(
+    env: &Env,
+    merchant_address: Address,
+    name: String,
+    requested_categories: Vec<Category>,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        MERCHANT_APPLIED,
+        no_campaign(env),
+        merchant_address.clone(),
+        MerchantAppliedEvent {
+            merchant_address,
+            name,
+            requested_categories,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_merchant_rejected(
+    env: &Env,
+    merchant_address: Address,
+    rejected_by: Address,
+    reason: String,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        MERCHANT_REJECTED,
+        no_campaign(env),
+        merchant_address.clone(),
+        MerchantRejectedEvent {
+            merchant_address,
+            rejected_by,
+            reason,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_merchant_approved(env: &Env, merchant_address: Address, approved_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        MERCHANT_APPROVED,
+        no_campaign(env),
+        merchant_address.clone(),
+        MerchantApprovedEvent {
+            merchant_address,
+            approved_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_merchant_revoked(env: &Env, merchant_address: Address, revoked_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        MERCHANT_REVOKED,
+        no_campaign(env),
+        merchant_address.clone(),
+        MerchantRevokedEvent {
+            merchant_address,
+            revoked_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_merchant_suspended(env: &Env, merchant_address: Address, suspended_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        MERCHANT_SUSPENDED,
+        no_campaign(env),
+        merchant_address.clone(),
+        MerchantSuspendedEvent {
+            merchant_address,
+            suspended_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_merchant_category_added(
+    env: &Env,
+    merchant_address: Address,
+    category: Category,
+    changed_by: Address,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        MERCHANT_CATEGORY_ADDED,
+        no_campaign(env),
+        merchant_address.clone(),
+        MerchantCategoryEvent {
+            merchant_address,
+            category,
+            changed_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_merchant_category_removed(
+    env: &Env,
+    merchant_address: Address,
+    category: Category,
+    changed_by: Address,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        MERCHANT_CATEGORY_REMOVED,
+        no_campaign(env),
+        merchant_address.clone(),
+        MerchantCategoryEvent {
+            merchant_address,
+            category,
+            changed_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_payment_recorded(
+    env: &Env,
+    merchant_address: Address,
+    amount: i128,
+    total_received: i128,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        PAYMENT_RECORDED,
+        no_campaign(env),
+        merchant_address.clone(),
+        PaymentRecordedEvent {
+            merchant_address,
+            amount,
+            total_received,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_category_defined(
+    env: &Env,
+    category: Category,
+    parent: Option<Category>,
+    display_name: String,
+    defined_by: Address,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        CATEGORY_DEFINED,
+        no_campaign(env),
+        defined_by.clone(),
+        CategoryDefinedEvent {
+            category,
+            parent: parent.into(),
+            display_name,
+            defined_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_category_updated(
+    env: &Env,
+    category: Category,
+    display_name: String,
+    active: bool,
+    updated_by: Address,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        CATEGORY_UPDATED,
+        no_campaign(env),
+        updated_by.clone(),
+        CategoryUpdatedEvent {
+            category,
+            display_name,
+            active,
+            updated_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_config_updated(env: &Env, setting: Symbol, value: Address, updated_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        CONFIG_UPDATED,
+        no_campaign(env),
+        updated_by.clone(),
+        ConfigUpdatedEvent {
+            setting,
+            value,
+            updated_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_contract_paused(env: &Env, scope: PauseScope, changed_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        CONTRACT_PAUSED,
+        no_campaign(env),
+        changed_by.clone(),
+        PauseEvent {
+            scope,
+            changed_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_contract_unpaused(env: &Env, scope: PauseScope, changed_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        CONTRACT_UNPAUSED,
+        no_campaign(env),
+        changed_by.clone(),
+        PauseEvent {
+            scope,
+            changed_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_contract_upgraded(env: &Env, wasm_hash: BytesN<32>, upgraded_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        CONTRACT_UPGRADED,
+        no_campaign(env),
+        upgraded_by.clone(),
+        ContractUpgradedEvent {
+            wasm_hash,
+            upgraded_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_schema_migrated(env: &Env, from_version: u32, to_version: u32, migrated_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        SCHEMA_MIGRATED,
+        no_campaign(env),
+        migrated_by.clone(),
+        SchemaMigratedEvent {
+            from_version,
+            to_version,
+            migrated_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_admin_proposed(
+    env: &Env,
+    current_admin: Address,
+    proposed_admin: Address,
+    expires_at: u64,
+) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        ADMIN_PROPOSED,
+        no_campaign(env),
+        proposed_admin.clone(),
+        AdminProposedEvent {
+            current_admin,
+            proposed_admin,
+            expires_at,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_admin_changed(env: &Env, previous_admin: Address, new_admin: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        ADMIN_CHANGED,
+        no_campaign(env),
+        new_admin.clone(),
+        AdminChangedEvent {
+            previous_admin,
+            new_admin,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_role_granted(env: &Env, role: Role, account: Address, changed_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        ROLE_GRANTED,
+        no_campaign(env),
+        account.clone(),
+        RoleEvent {
+            role,
+            account,
+            changed_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_role_revoked(env: &Env, role: Role, account: Address, changed_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        ROLE_REVOKED,
+        no_campaign(env),
+        account.clone(),
+        RoleEvent {
+            role,
+            account,
+            changed_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_ttl_config_updated(env: &Env, config: TtlConfig, updated_by: Address) {
+    let timestamp = env.ledger().timestamp();
+    publish(
+        env,
+        TTL_CONFIG_UPDATED,
+        no_campaign(env),
+        updated_by.clone(),
+        TtlConfigUpdatedEvent {
+            config,
+            updated_by,
+            timestamp,
+        },
+    );
+}
+
+pub fn emit_audit_trail(env: &Env, event_type: Symbol, actor: Address) {
+    emit_campaign_audit_trail(env, event_type, no_campaign(env), actor);
+}
+
+pub fn emit_campaign_audit_trail(
     env: &Env,
     event_type: Symbol,
     campaign_id: String,
     actor: Address,
-    details: String,
 ) {
     let timestamp = env.ledger().timestamp();
-    env.events().publish(
-        (AUDIT_TRAIL, event_type.clone()),
+    publish(
+        env,
+        AUDIT_TRAIL,
+        campaign_id.clone(),
+        actor.clone(),
         AuditTrailEvent {
             event_type,
             campaign_id,
             actor,
-            details,
             timestamp,
         },
     );
 }
-
@@ -1,11 +1,23 @@
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, String, Symbol, symbol_short, Vec};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map, String, Symbol, symbol_short, Vec};
+use crate::admin::{self, PendingAdmin};
 use crate::error::Error;
 use crate::event;
+use crate::pause;
+use crate::rbac;
+use crate::registry::CategoryRegistryClient;
+use crate::ttl::{self, TtlConfig};
+use crate::types::{Category, Operation, PauseScope, Role};
+use crate::upgrade;
 
 // Storage keys (max 9 chars for symbol_short!)
 const ADMIN: Symbol = symbol_short!("ADMIN");
 const MERCHANTS: Symbol = symbol_short!("MERCHANTS");
 const INIT: Symbol = symbol_short!("INIT");
+const VAULT: Symbol = symbol_short!("VAULT");
+const CATEGORIES: Symbol = symbol_short!("CAT_REG");
+
+/// Storage layout version written by this code
+const SCHEMA_VERSION: u32 = 2;
 
 /// Merchant Status enum
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -16,6 +28,7 @@ pub enum MerchantStatus {
     Approved = 1,
     Suspended = 2,
     Revoked = 3,
+    Rejected = 4,
 }
 
 /// Merchant Information structure
@@ -24,11 +37,26 @@ pub enum MerchantStatus {
 pub struct MerchantInfo {
     pub address: Address,
     pub name: String,
-    pub approved_categories: Vec<Symbol>,
+    pub approved_categories: Vec<Category>,
     pub status: MerchantStatus,
     pub registered_at: u64,
     pub total_received: i128,
     pub transaction_count: u32,
+    pub rejection_reason: String,
+}
+
+/// Merchant as stored under schema version 1, with symbol categories and
+/// no rejection reason
+#[derive(Clone)]
+#[contracttype]
+struct LegacyMerchantInfo {
+    address: Address,
+    name: String,
+    approved_categories: Vec<Symbol>,
+    status: MerchantStatus,
+    registered_at: u64,
+    total_received: i128,
+    transaction_count: u32,
 }
 
 /// Merchant Registry Contract
@@ -41,7 +69,7 @@ impl MerchantRegistry {
     /// Initialize the merchant registry
     pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
         // Check if already initialized
-        if env.storage().instance().has(&INIT) {
+        if env.storage().instance().has(&INIT) || upgrade::has_version(&env) {
             return Err(Error::AlreadyInitialized);
         }
 
@@ -57,23 +85,28 @@ impl MerchantRegistry {
 
         // Mark as initialized
         env.storage().instance().set(&INIT, &true);
+        upgrade::set_version(&env, SCHEMA_VERSION);
+        ttl::extend_instance(&env);
+
+        event::emit_config_updated(&env, symbol_short!("admin"), admin.clone(), admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
 
         Ok(())
     }
 
-    /// Register a new merchant (admin only)
+    /// Register a new merchant (verifier or admin)
     pub fn register_merchant(
         env: Env,
+        caller: Address,
         merchant_address: Address,
         name: String,
-        categories: Vec<Symbol>,
+        categories: Vec<Category>,
     ) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get merchant map
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
@@ -86,15 +119,20 @@ impl MerchantRegistry {
             return Err(Error::AlreadyInitialized);
         }
 
+        // Every category must be active in the taxonomy
+        let categories = Category::normalize_all(&env, &categories)?;
+        Self::require_active_categories(&env, &categories)?;
+
         // Create merchant info
         let merchant_info = MerchantInfo {
             address: merchant_address.clone(),
             name: name.clone(),
-            approved_categories: categories,
+            approved_categories: categories.clone(),
             status: MerchantStatus::Approved,
             registered_at: env.ledger().timestamp(),
             total_received: 0,
             transaction_count: 0,
+            rejection_reason: String::from_str(&env, ""),
         };
 
         // Store merchant
@@ -102,8 +140,8 @@ impl MerchantRegistry {
         env.storage().instance().set(&MERCHANTS, &merchants);
 
         // Emit registration event
-        let categories_str = String::from_str(&env, "multiple");
-        event::emit_merchant_registered(&env, merchant_address, name, categories_str);
+        event::emit_merchant_registered(&env, merchant_address, name, categories);
+        event::emit_audit_trail(&env, event::MERCHANT_REGISTERED, caller);
 
         Ok(())
     }
@@ -111,11 +149,16 @@ impl MerchantRegistry {
     /// Merchant self-registration (pending approval)
     pub fn register_merchant_pending(
         env: Env,
+        merchant_address: Address,
         name: String,
-        categories: Vec<Symbol>,
+        categories: Vec<Category>,
     ) -> Result<(), Error> {
-        // Get caller as merchant address
-        let merchant_address = env.current_contract_address();
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Merchant must authorize their own registration
+        merchant_address.require_auth();
+        let categories = Category::normalize_all(&env, &categories)?;
 
         // Get merchant map
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
@@ -128,36 +171,36 @@ impl MerchantRegistry {
             return Err(Error::AlreadyInitialized);
         }
 
-        // Create merchant info (pending)
+        // Create merchant info (pending), holding the requested categories
         let merchant_info = MerchantInfo {
             address: merchant_address.clone(),
             name: name.clone(),
-            approved_categories: categories,
+            approved_categories: categories.clone(),
             status: MerchantStatus::Pending,
             registered_at: env.ledger().timestamp(),
             total_received: 0,
             transaction_count: 0,
+            rejection_reason: String::from_str(&env, ""),
         };
 
         // Store merchant
         merchants.set(merchant_address.clone(), merchant_info);
         env.storage().instance().set(&MERCHANTS, &merchants);
 
-        // Emit registration event
-        let categories_str = String::from_str(&env, "pending");
-        event::emit_merchant_registered(&env, merchant_address, name, categories_str);
+        // Emit application event; approval is announced separately
+        event::emit_merchant_applied(&env, merchant_address.clone(), name, categories);
+        event::emit_audit_trail(&env, event::MERCHANT_APPLIED, merchant_address);
 
         Ok(())
     }
 
-    /// Approve a pending merchant (admin only)
-    pub fn approve_merchant(env: Env, merchant_address: Address) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+    /// Approve a pending merchant (verifier or admin)
+    pub fn approve_merchant(env: Env, caller: Address, merchant_address: Address) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get merchant map
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
@@ -169,28 +212,87 @@ impl MerchantRegistry {
         let mut merchant_info = merchants.get(merchant_address.clone())
             .ok_or(Error::MerchantNotApproved)?;
 
+        // Only pending or suspended merchants can be approved
+        if merchant_info.status != MerchantStatus::Pending
+            && merchant_info.status != MerchantStatus::Suspended
+        {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        // Requested categories are validated when first approved
+        if merchant_info.status == MerchantStatus::Pending {
+            Self::require_active_categories(&env, &merchant_info.approved_categories)?;
+        }
+
         // Update status
         merchant_info.status = MerchantStatus::Approved;
 
         // Save updated info
-        merchants.set(merchant_address, merchant_info);
+        merchants.set(merchant_address.clone(), merchant_info);
         env.storage().instance().set(&MERCHANTS, &merchants);
 
+        // Emit approval event
+        event::emit_merchant_approved(&env, merchant_address, caller.clone());
+        event::emit_audit_trail(&env, event::MERCHANT_APPROVED, caller);
+
         Ok(())
     }
 
-    /// Add category approval to merchant (admin only)
-    pub fn approve_for_category(
+    /// Reject a pending merchant with a reason (verifier or admin)
+    pub fn reject_merchant(
         env: Env,
+        caller: Address,
         merchant_address: Address,
-        category: Symbol,
+        reason: String,
     ) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
+
+        // Get merchant map
+        let mut merchants: Map<Address, MerchantInfo> = env.storage()
             .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+            .get(&MERCHANTS)
+            .ok_or(Error::MerchantNotApproved)?;
+
+        // Get merchant info
+        let mut merchant_info = merchants.get(merchant_address.clone())
+            .ok_or(Error::MerchantNotApproved)?;
+
+        // Verify status is pending
+        if merchant_info.status != MerchantStatus::Pending {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        // Update merchant info
+        merchant_info.status = MerchantStatus::Rejected;
+        merchant_info.rejection_reason = reason.clone();
+
+        // Save updated info
+        merchants.set(merchant_address.clone(), merchant_info);
+        env.storage().instance().set(&MERCHANTS, &merchants);
+
+        // Emit rejection event
+        event::emit_merchant_rejected(&env, merchant_address, caller.clone(), reason);
+        event::emit_audit_trail(&env, event::MERCHANT_REJECTED, caller);
+
+        Ok(())
+    }
+
+    /// Add category approval to merchant (verifier or admin)
+    pub fn approve_for_category(
+        env: Env,
+        caller: Address,
+        merchant_address: Address,
+        category: Category,
+    ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get merchant map
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
@@ -202,6 +304,10 @@ impl MerchantRegistry {
         let mut merchant_info = merchants.get(merchant_address.clone())
             .ok_or(Error::MerchantNotApproved)?;
 
+        // Category must be active in the taxonomy
+        let category = category.normalize(&env)?;
+        Self::require_active_categories(&env, &Vec::from_array(&env, [category.clone()]))?;
+
         // Check if category already exists
         let mut has_category = false;
         for cat in merchant_info.approved_categories.iter() {
@@ -213,28 +319,31 @@ impl MerchantRegistry {
 
         // Add category if not exists
         if !has_category {
-            merchant_info.approved_categories.push_back(category);
-        }
+            merchant_info.approved_categories.push_back(category.clone());
 
-        // Save updated info
-        merchants.set(merchant_address, merchant_info);
-        env.storage().instance().set(&MERCHANTS, &merchants);
+            // Save updated info
+            merchants.set(merchant_address.clone(), merchant_info);
+            env.storage().instance().set(&MERCHANTS, &merchants);
+
+            event::emit_merchant_category_added(&env, merchant_address, category, caller.clone());
+            event::emit_audit_trail(&env, event::MERCHANT_CATEGORY_ADDED, caller);
+        }
 
         Ok(())
     }
 
-    /// Remove category from merchant (admin only)
+    /// Remove category from merchant (verifier or admin)
     pub fn remove_category(
         env: Env,
+        caller: Address,
         merchant_address: Address,
-        category: Symbol,
+        category: Category,
     ) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get merchant map
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
@@ -247,17 +356,24 @@ impl MerchantRegistry {
             .ok_or(Error::MerchantNotApproved)?;
 
         // Create new categories without the removed one
-        let mut new_categories: Vec<Symbol> = Vec::new(&env);
+        let category = category.normalize(&env)?;
+        let mut new_categories: Vec<Category> = Vec::new(&env);
         for cat in merchant_info.approved_categories.iter() {
             if cat != category {
                 new_categories.push_back(cat);
             }
         }
-        merchant_info.approved_categories = new_categories;
 
-        // Save updated info
-        merchants.set(merchant_address, merchant_info);
-        env.storage().instance().set(&MERCHANTS, &merchants);
+        if new_categories.len() != merchant_info.approved_categories.len() {
+            merchant_info.approved_categories = new_categories;
+
+            // Save updated info
+            merchants.set(merchant_address.clone(), merchant_info);
+            env.storage().instance().set(&MERCHANTS, &merchants);
+
+            event::emit_merchant_category_removed(&env, merchant_address, category, caller.clone());
+            event::emit_audit_trail(&env, event::MERCHANT_CATEGORY_REMOVED, caller);
+        }
 
         Ok(())
     }
@@ -266,8 +382,15 @@ impl MerchantRegistry {
     pub fn is_approved_for_category(
         env: Env,
         merchant_address: Address,
-        category: Symbol,
+        category: Category,
     ) -> bool {
+        ttl::extend_instance(&env);
+
+        let category = match category.normalize(&env) {
+            Ok(c) => c,
+            Err(_) => return false,
+        };
+
         let merchants: Map<Address, MerchantInfo> = match env.storage().instance().get(&MERCHANTS) {
             Some(m) => m,
             None => return false,
@@ -293,6 +416,8 @@ impl MerchantRegistry {
 
     /// Check if merchant is approved (any status)
     pub fn is_approved(env: Env, merchant_address: Address) -> bool {
+        ttl::extend_instance(&env);
+
         let merchants: Map<Address, MerchantInfo> = match env.storage().instance().get(&MERCHANTS) {
             Some(m) => m,
             None => return false,
@@ -304,14 +429,12 @@ impl MerchantRegistry {
         }
     }
 
-    /// Revoke merchant (admin only)
-    pub fn revoke_merchant(env: Env, merchant_address: Address) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+    /// Revoke merchant (verifier or admin)
+    pub fn revoke_merchant(env: Env, caller: Address, merchant_address: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get merchant map
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
@@ -327,20 +450,22 @@ impl MerchantRegistry {
         merchant_info.status = MerchantStatus::Revoked;
 
         // Save updated info
-        merchants.set(merchant_address, merchant_info);
+        merchants.set(merchant_address.clone(), merchant_info);
         env.storage().instance().set(&MERCHANTS, &merchants);
 
+        // Emit revocation event
+        event::emit_merchant_revoked(&env, merchant_address, caller.clone());
+        event::emit_audit_trail(&env, event::MERCHANT_REVOKED, caller);
+
         Ok(())
     }
 
-    /// Suspend merchant (admin only)
-    pub fn suspend_merchant(env: Env, merchant_address: Address) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+    /// Suspend merchant (verifier or admin)
+    pub fn suspend_merchant(env: Env, caller: Address, merchant_address: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get merchant map
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
@@ -356,9 +481,13 @@ impl MerchantRegistry {
         merchant_info.status = MerchantStatus::Suspended;
 
         // Save updated info
-        merchants.set(merchant_address, merchant_info);
+        merchants.set(merchant_address.clone(), merchant_info);
         env.storage().instance().set(&MERCHANTS, &merchants);
 
+        // Emit suspension event
+        event::emit_merchant_suspended(&env, merchant_address, caller.clone());
+        event::emit_audit_trail(&env, event::MERCHANT_SUSPENDED, caller);
+
         Ok(())
     }
 
@@ -368,12 +497,192 @@ impl MerchantRegistry {
         merchants.get(merchant_address)
     }
 
-    /// Update merchant received amount (called after transaction)
+    /// Pause every state-changing operation, or only one (operator, auditor or admin)
+    pub fn pause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator or auditor
+        Self::require_role(&env, &caller, &[Role::Operator, Role::Auditor])?;
+
+        pause::set_paused(&env, scope, true, caller);
+        Ok(())
+    }
+
+    /// Lift a pause set for the same scope (operator or admin)
+    ///
+    /// Unpausing one operation leaves a contract-wide pause in place.
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        pause::set_paused(&env, scope, false, caller);
+        Ok(())
+    }
+
+    /// Check whether an operation may not run because of a pause
+    ///
+    /// `All` reports only a contract-wide pause.
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        pause::is_paused(&env, scope)
+    }
+
+    /// Propose a new admin, who takes over once they accept (admin only)
+    ///
+    /// `expires_at` is the ledger timestamp after which the proposal can no
+    /// longer be accepted; 0 means it never lapses. A new proposal replaces
+    /// the previous one.
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: u64) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        admin::propose(&env, admin, new_admin, expires_at)
+    }
+
+    /// Accept a pending admin proposal (proposed admin only)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let new_admin = admin::accept(&env)?;
+        env.storage().instance().set(&ADMIN, &new_admin);
+        Ok(())
+    }
+
+    /// Get the current admin
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ADMIN)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<PendingAdmin> {
+        admin::get_pending(&env)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::grant(&env, role, account, admin)
+    }
+
+    /// Revoke a role from an account (admin only)
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::revoke(&env, role, account, admin)
+    }
+
+    /// Check whether an account holds a role; the admin holds every role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        match env.storage().instance().get::<_, Address>(&ADMIN) {
+            Some(admin) => rbac::has_role(&env, &admin, role, &account),
+            None => false,
+        }
+    }
+
+    /// Replace this contract's code with an uploaded wasm (admin only)
+    ///
+    /// Storage is kept; call `migrate` afterwards to convert it if the new
+    /// code changed its layout.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::upgrade(&env, new_wasm_hash, admin);
+        Ok(())
+    }
+
+    /// Schema version of the data in storage
+    pub fn version(env: Env) -> u32 {
+        upgrade::stored_version(&env)
+    }
+
+    /// Convert stored data to this code's schema version (admin only)
+    ///
+    /// Returns the version now in storage.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::migrate(&env, SCHEMA_VERSION, Self::migrate_step, admin)
+    }
+
+    /// Set how far and how often storage lifetimes are extended (admin only)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        ttl::set_config(&env, config, admin)
+    }
+
+    /// Get the storage lifetime settings
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl::get_config(&env)
+    }
+
+    /// Set the ReliefVault allowed to record payments (admin only)
+    pub fn set_vault(env: Env, vault: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        env.storage().instance().set(&VAULT, &vault);
+
+        event::emit_config_updated(&env, symbol_short!("vault"), vault, admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
+
+        Ok(())
+    }
+
+    /// Get the ReliefVault allowed to record payments
+    pub fn get_vault(env: Env) -> Option<Address> {
+        env.storage().instance().get(&VAULT)
+    }
+
+    /// Set the CategoryRegistry categories are validated against (admin only)
+    pub fn set_category_registry(env: Env, category_registry: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        env.storage().instance().set(&CATEGORIES, &category_registry);
+
+        event::emit_config_updated(&env, symbol_short!("cat_reg"), category_registry, admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
+
+        Ok(())
+    }
+
+    /// Get the CategoryRegistry categories are validated against
+    pub fn get_category_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&CATEGORIES)
+    }
+
+    /// Update merchant received amount (called by the vault after a transaction)
     pub fn update_received(
         env: Env,
         merchant_address: Address,
         amount: i128,
     ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Spending)?;
+        ttl::extend_instance(&env);
+
+        // Only the authorised vault may record payments
+        let vault: Address = env.storage()
+            .instance()
+            .get(&VAULT)
+            .ok_or(Error::Unauthorized)?;
+        vault.require_auth();
+
         let mut merchants: Map<Address, MerchantInfo> = env.storage()
             .instance()
             .get(&MERCHANTS)
@@ -384,10 +693,14 @@ impl MerchantRegistry {
 
         merchant_info.total_received += amount;
         merchant_info.transaction_count += 1;
+        let total_received = merchant_info.total_received;
 
-        merchants.set(merchant_address, merchant_info);
+        merchants.set(merchant_address.clone(), merchant_info);
         env.storage().instance().set(&MERCHANTS, &merchants);
 
+        event::emit_payment_recorded(&env, merchant_address, amount, total_received);
+        event::emit_audit_trail(&env, event::PAYMENT_RECORDED, vault);
+
         Ok(())
     }
 
@@ -406,7 +719,12 @@ impl MerchantRegistry {
     }
 
     /// Get merchants by category
-    pub fn get_merchants_by_category(env: Env, category: Symbol) -> Vec<MerchantInfo> {
+    pub fn get_merchants_by_category(env: Env, category: Category) -> Vec<MerchantInfo> {
+        let category = match category.normalize(&env) {
+            Ok(c) => c,
+            Err(_) => return Vec::new(&env),
+        };
+
         let merchants: Map<Address, MerchantInfo> = match env.storage().instance().get(&MERCHANTS) {
             Some(m) => m,
             None => return Vec::new(&env),
@@ -442,3 +760,245 @@ impl MerchantRegistry {
         result
     }
 }
+
+impl MerchantRegistry {
+    /// Rewrite entries stored under schema `from` in the layout of `from + 1`
+    ///
+    /// Version 2 names categories with the shared `Category` type and
+    /// records why a merchant was rejected; merchants of version 1 start
+    /// with no reason.
+    fn migrate_step(env: &Env, from: u32) {
+        if from != 1 {
+            return;
+        }
+
+        let legacy: Map<Address, LegacyMerchantInfo> = env.storage()
+            .instance()
+            .get(&MERCHANTS)
+            .unwrap_or(Map::new(env));
+        let mut merchants: Map<Address, MerchantInfo> = Map::new(env);
+        for (address, info) in legacy.iter() {
+            let mut approved_categories: Vec<Category> = Vec::new(env);
+            for name in info.approved_categories.iter() {
+                if let Ok(category) = Category::from_symbol(env, &name) {
+                    approved_categories.push_back(category);
+                }
+            }
+            merchants.set(address, MerchantInfo {
+                address: info.address,
+                name: info.name,
+                approved_categories,
+                status: info.status,
+                registered_at: info.registered_at,
+                total_received: info.total_received,
+                transaction_count: info.transaction_count,
+                rejection_reason: String::from_str(env, ""),
+            });
+        }
+        env.storage().instance().set(&MERCHANTS, &merchants);
+    }
+
+    /// Require the stored admin's authorization
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Require the caller's authorization and one of `roles`
+    fn require_role(env: &Env, caller: &Address, roles: &[Role]) -> Result<(), Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        rbac::require_role(env, &admin, caller, roles)
+    }
+
+    /// Check every category is active in the configured CategoryRegistry
+    fn require_active_categories(env: &Env, categories: &Vec<Category>) -> Result<(), Error> {
+        let address: Address = env.storage()
+            .instance()
+            .get(&CATEGORIES)
+            .ok_or(Error::CategoryRegistryNotSet)?;
+        let category_registry = CategoryRegistryClient::new(env, &address);
+        for category in categories.iter() {
+            if !category_registry.is_active(&category) {
+                return Err(Error::InvalidCategory);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::testutils::{MockCategories, MockCategoriesClient};
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
+    use soroban_sdk::IntoVal;
+
+    fn setup(env: &Env) -> (MerchantRegistryClient<'_>, MockCategoriesClient<'_>) {
+        env.mock_all_auths();
+
+        let registry_id = env.register_contract(None, MerchantRegistry);
+        let registry = MerchantRegistryClient::new(env, &registry_id);
+        registry.initialize(&Address::generate(env));
+        let categories = MockCategoriesClient::new(env, &env.register_contract(None, MockCategories));
+        registry.set_category_registry(&categories.address);
+        (registry, categories)
+    }
+
+    #[test]
+    fn test_update_received_gated_to_vault() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let merchant = Address::generate(&env);
+        let vault = Address::generate(&env);
+        let categories = Vec::from_array(&env, [Category::new(&env, "food").unwrap()]);
+        registry.register_merchant(&admin, &merchant, &String::from_str(&env, "Corner Shop"), &categories);
+
+        assert_eq!(
+            registry.try_update_received(&merchant, &100),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        registry.set_vault(&vault);
+        registry.update_received(&merchant, &100);
+        let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(signers, std::vec![vault]);
+        assert_eq!(registry.get_merchant_info(&merchant).unwrap().total_received, 100);
+    }
+
+    #[test]
+    fn test_self_onboarding_and_review() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let verifier = Address::generate(&env);
+        registry.grant_role(&Role::Verifier, &verifier);
+        let shop = Address::generate(&env);
+        let pharmacy = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let medicine = Category::new(&env, "medicine").unwrap();
+
+        registry.register_merchant_pending(&shop, &String::from_str(&env, "Shop"), &Vec::from_array(&env, [food.clone()]));
+        registry.register_merchant_pending(&pharmacy, &String::from_str(&env, "Pharmacy"), &Vec::from_array(&env, [medicine.clone()]));
+        let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(signers, std::vec![pharmacy.clone()]);
+        let events = env.events().all();
+        let (_, topics, data) = events.get(events.len() - 2).unwrap();
+        assert_eq!(topics, (event::MERCHANT_APPLIED, String::from_str(&env, ""), pharmacy.clone()).into_val(&env));
+        let (_, applied): (u32, event::MerchantAppliedEvent) = data.into_val(&env);
+        assert_eq!(applied.requested_categories, Vec::from_array(&env, [medicine.clone()]));
+        assert_eq!(registry.get_pending_merchants().len(), 2);
+        assert!(!registry.is_approved_for_category(&shop, &food));
+
+        registry.approve_merchant(&verifier, &shop);
+        registry.reject_merchant(&verifier, &pharmacy, &String::from_str(&env, "licence expired"));
+
+        assert!(registry.is_approved_for_category(&shop, &food));
+        let rejected = registry.get_merchant_info(&pharmacy).unwrap();
+        assert!(rejected.status == MerchantStatus::Rejected);
+        assert_eq!(rejected.rejection_reason, String::from_str(&env, "licence expired"));
+        assert_eq!(registry.get_pending_merchants().len(), 0);
+        assert_eq!(
+            registry.try_approve_merchant(&verifier, &pharmacy),
+            Err(Ok(Error::InvalidStatusTransition))
+        );
+    }
+
+    #[test]
+    fn test_categories_validated_against_taxonomy() {
+        let env = Env::default();
+        let (registry, categories) = setup(&env);
+        let admin = registry.get_admin().unwrap();
+        let shop = Address::generate(&env);
+        let food = Category::new(&env, "food").unwrap();
+        let fuel = Category::new(&env, "fuel").unwrap();
+        categories.retire(&fuel);
+
+        assert_eq!(
+            registry.try_register_merchant(&admin, &shop, &String::from_str(&env, "Shop"), &Vec::from_array(&env, [fuel.clone()])),
+            Err(Ok(Error::InvalidCategory))
+        );
+        assert_eq!(
+            registry.try_register_merchant(&admin, &shop, &String::from_str(&env, "Shop"), &Vec::from_array(&env, [Category::unchecked(&env, "food aid")])),
+            Err(Ok(Error::InvalidCategory))
+        );
+        registry.register_merchant(&admin, &shop, &String::from_str(&env, "Shop"), &Vec::from_array(&env, [Category::unchecked(&env, "Food")]));
+        assert_eq!(registry.get_merchant_info(&shop).unwrap().approved_categories, Vec::from_array(&env, [food.clone()]));
+        assert!(registry.is_approved_for_category(&shop, &Category::unchecked(&env, "FOOD")));
+        assert!(!registry.is_approved_for_category(&shop, &Category::unchecked(&env, "")));
+        assert_eq!(
+            registry.try_approve_for_category(&admin, &shop, &fuel),
+            Err(Ok(Error::InvalidCategory))
+        );
+    }
+
+    #[test]
+    fn test_migrate_rewrites_merchants() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let shop = Address::generate(&env);
+
+        // Simulate a merchant registered before schema version 2
+        env.as_contract(&registry.address, || {
+            let mut merchants: Map<Address, LegacyMerchantInfo> = Map::new(&env);
+            merchants.set(shop.clone(), LegacyMerchantInfo {
+                address: shop.clone(),
+                name: String::from_str(&env, "Shop"),
+                approved_categories: Vec::from_array(&env, [Symbol::new(&env, "Food"), Symbol::new(&env, "medicine")]),
+                status: MerchantStatus::Approved,
+                registered_at: 0,
+                total_received: 700,
+                transaction_count: 3,
+            });
+            env.storage().instance().set(&MERCHANTS, &merchants);
+            upgrade::set_version(&env, 1);
+        });
+
+        assert_eq!(registry.migrate(), 2);
+        let info = registry.get_merchant_info(&shop).unwrap();
+        assert_eq!(
+            info.approved_categories,
+            Vec::from_array(&env, [Category::new(&env, "food").unwrap(), Category::new(&env, "medicine").unwrap()])
+        );
+        assert_eq!(info.total_received, 700);
+        assert_eq!(info.rejection_reason, String::from_str(&env, ""));
+        assert!(registry.is_approved_for_category(&shop, &Category::new(&env, "food").unwrap()));
+    }
+
+    #[test]
+    fn test_two_step_admin_transfer() {
+        let env = Env::default();
+        let (registry, _) = setup(&env);
+        let old_admin = registry.get_admin().unwrap();
+        let new_admin = Address::generate(&env);
+        let merchant = Address::generate(&env);
+
+        assert_eq!(registry.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+        // A lapsed proposal cannot be accepted
+        registry.propose_admin(&new_admin, &100);
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        assert_eq!(registry.try_accept_admin(), Err(Ok(Error::AdminProposalExpired)));
+        assert_eq!(registry.get_admin(), Some(old_admin));
+
+        registry.propose_admin(&new_admin, &0);
+        registry.accept_admin();
+        let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(signers, std::vec![new_admin.clone()]);
+        assert_eq!(registry.get_admin(), Some(new_admin.clone()));
+        assert_eq!(registry.get_pending_admin(), None);
+
+        registry.register_merchant_pending(&merchant, &String::from_str(&env, "Corner Shop"), &Vec::new(&env));
+        registry.approve_merchant(&new_admin, &merchant);
+        let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(signers, std::vec![new_admin]);
+    }
+}
@@ -1,12 +1,32 @@
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, String, Symbol, symbol_short, Vec};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map, String, Symbol, symbol_short, Vec};
+use crate::admin::{self, PendingAdmin};
 use crate::error::Error;
 use crate::event;
+use crate::pause;
+use crate::rbac;
+use crate::ttl::{self, TtlConfig};
+use crate::types::{Operation, PauseScope, Role};
+use crate::upgrade;
 
 // Storage keys (max 9 chars for symbol_short!)
 const ADMIN: Symbol = symbol_short!("ADMIN");
 const NGOS: Symbol = symbol_short!("NGOS");
 const INIT: Symbol = symbol_short!("INIT");
 
+/// Storage layout version written by this code
+const SCHEMA_VERSION: u32 = 2;
+
+/// Longest registration number accepted, in bytes
+const MAX_REGISTRATION_NUMBER_LEN: u32 = 64;
+
+/// Per-entry storage keys
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// NGO that registered a legal registration number
+    RegistrationNumber(String),
+}
+
 /// NGO Status enum
 #[derive(Clone, Copy, PartialEq, Eq)]
 #[contracttype]
@@ -42,7 +62,7 @@ impl NGORegistry {
     /// Initialize the NGO registry
     pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
         // Check if already initialized
-        if env.storage().instance().has(&INIT) {
+        if env.storage().instance().has(&INIT) || upgrade::has_version(&env) {
             return Err(Error::AlreadyInitialized);
         }
 
@@ -58,6 +78,11 @@ impl NGORegistry {
 
         // Mark as initialized
         env.storage().instance().set(&INIT, &true);
+        upgrade::set_version(&env, SCHEMA_VERSION);
+        ttl::extend_instance(&env);
+
+        event::emit_config_updated(&env, symbol_short!("admin"), admin.clone(), admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
 
         Ok(())
     }
@@ -65,15 +90,16 @@ impl NGORegistry {
     /// Register a new NGO (self-registration)
     pub fn register_ngo(
         env: Env,
+        ngo_address: Address,
         name: String,
         registration_number: String,
         country: String,
     ) -> Result<(), Error> {
-        // Get caller as the NGO address
-        let ngo_address = env.current_contract_address();
-        
-        // For demo, we use a simpler approach - caller registers themselves
-        // In production, you'd use invoker address
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // NGO must authorize their own registration
+        ngo_address.require_auth();
 
         // Get NGO map
         let mut ngos: Map<Address, NGOInfo> = env.storage()
@@ -83,7 +109,15 @@ impl NGORegistry {
 
         // Check if already registered
         if ngos.contains_key(ngo_address.clone()) {
-            return Err(Error::AlreadyInitialized);
+            return Err(Error::NgoAlreadyRegistered);
+        }
+
+        // One legal entity may only register one wallet, however the number is written
+        let registration_number = Self::normalize_registration_number(&env, &registration_number)
+            .ok_or(Error::InvalidRegistrationNumber)?;
+        let reg_key = DataKey::RegistrationNumber(registration_number.clone());
+        if env.storage().persistent().has(&reg_key) {
+            return Err(Error::DuplicateRegistration);
         }
 
         // Create NGO info
@@ -101,14 +135,17 @@ impl NGORegistry {
         // Store NGO
         ngos.set(ngo_address.clone(), ngo_info);
         env.storage().instance().set(&NGOS, &ngos);
+        env.storage().persistent().set(&reg_key, &ngo_address);
+        ttl::extend_persistent(&env, &reg_key);
 
         // Emit registration event
-        event::emit_ngo_registered(&env, ngo_address, name, country);
+        event::emit_ngo_registered(&env, ngo_address.clone(), name, country);
+        event::emit_audit_trail(&env, event::NGO_REGISTERED, ngo_address);
 
         Ok(())
     }
 
-    /// Register NGO with specific address (for external callers)
+    /// Register NGO with specific address (alias of register_ngo)
     pub fn register_ngo_with_address(
         env: Env,
         ngo_address: Address,
@@ -116,50 +153,24 @@ impl NGORegistry {
         registration_number: String,
         country: String,
     ) -> Result<(), Error> {
-        // NGO must authorize their own registration
-        ngo_address.require_auth();
-
-        // Get NGO map
-        let mut ngos: Map<Address, NGOInfo> = env.storage()
-            .instance()
-            .get(&NGOS)
-            .unwrap_or(Map::new(&env));
-
-        // Check if already registered
-        if ngos.contains_key(ngo_address.clone()) {
-            return Err(Error::AlreadyInitialized);
-        }
-
-        // Create NGO info
-        let ngo_info = NGOInfo {
-            address: ngo_address.clone(),
-            name: name.clone(),
-            registration_number,
-            country: country.clone(),
-            status: NGOStatus::Pending,
-            registered_at: env.ledger().timestamp(),
-            verified_at: 0,
-            total_campaigns: 0,
-        };
-
-        // Store NGO
-        ngos.set(ngo_address.clone(), ngo_info);
-        env.storage().instance().set(&NGOS, &ngos);
-
-        // Emit registration event
-        event::emit_ngo_registered(&env, ngo_address, name, country);
+        Self::register_ngo(env, ngo_address, name, registration_number, country)
+    }
 
-        Ok(())
+    /// Get the NGO registered under a legal registration number
+    pub fn get_ngo_by_registration(env: Env, registration_number: String) -> Option<Address> {
+        let registration_number = Self::normalize_registration_number(&env, &registration_number)?;
+        env.storage()
+            .persistent()
+            .get(&DataKey::RegistrationNumber(registration_number))
     }
 
-    /// Verify an NGO (admin only)
-    pub fn verify_ngo(env: Env, ngo_address: Address) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+    /// Verify an NGO (verifier or admin)
+    pub fn verify_ngo(env: Env, caller: Address, ngo_address: Address) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Registrations)?;
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get NGO map
         let mut ngos: Map<Address, NGOInfo> = env.storage()
@@ -180,19 +191,18 @@ impl NGORegistry {
         env.storage().instance().set(&NGOS, &ngos);
 
         // Emit verification event
-        event::emit_ngo_verified(&env, ngo_address, admin);
+        event::emit_ngo_verified(&env, ngo_address, caller.clone());
+        event::emit_audit_trail(&env, event::NGO_VERIFIED, caller);
 
         Ok(())
     }
 
-    /// Revoke an NGO (admin only)
-    pub fn revoke_ngo(env: Env, ngo_address: Address) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+    /// Revoke an NGO (verifier or admin)
+    pub fn revoke_ngo(env: Env, caller: Address, ngo_address: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get NGO map
         let mut ngos: Map<Address, NGOInfo> = env.storage()
@@ -211,17 +221,19 @@ impl NGORegistry {
         ngos.set(ngo_address.clone(), ngo_info);
         env.storage().instance().set(&NGOS, &ngos);
 
+        // Emit revocation event
+        event::emit_ngo_revoked(&env, ngo_address, caller.clone());
+        event::emit_audit_trail(&env, event::NGO_REVOKED, caller);
+
         Ok(())
     }
 
-    /// Suspend an NGO (admin only)
-    pub fn suspend_ngo(env: Env, ngo_address: Address) -> Result<(), Error> {
-        // Get and verify admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&ADMIN)
-            .ok_or(Error::Unauthorized)?;
-        admin.require_auth();
+    /// Suspend an NGO (verifier or admin)
+    pub fn suspend_ngo(env: Env, caller: Address, ngo_address: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be a verifier
+        Self::require_role(&env, &caller, &[Role::Verifier])?;
 
         // Get NGO map
         let mut ngos: Map<Address, NGOInfo> = env.storage()
@@ -240,11 +252,149 @@ impl NGORegistry {
         ngos.set(ngo_address.clone(), ngo_info);
         env.storage().instance().set(&NGOS, &ngos);
 
+        // Emit suspension event
+        event::emit_ngo_suspended(&env, ngo_address, caller.clone());
+        event::emit_audit_trail(&env, event::NGO_SUSPENDED, caller);
+
+        Ok(())
+    }
+
+    /// Pause every state-changing operation, or only one (operator, auditor or admin)
+    pub fn pause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator or auditor
+        Self::require_role(&env, &caller, &[Role::Operator, Role::Auditor])?;
+
+        pause::set_paused(&env, scope, true, caller);
+        Ok(())
+    }
+
+    /// Lift a pause set for the same scope (operator or admin)
+    ///
+    /// Unpausing one operation leaves a contract-wide pause in place.
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        pause::set_paused(&env, scope, false, caller);
+        Ok(())
+    }
+
+    /// Check whether an operation may not run because of a pause
+    ///
+    /// `All` reports only a contract-wide pause.
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        pause::is_paused(&env, scope)
+    }
+
+    /// Propose a new admin, who takes over once they accept (admin only)
+    ///
+    /// `expires_at` is the ledger timestamp after which the proposal can no
+    /// longer be accepted; 0 means it never lapses. A new proposal replaces
+    /// the previous one.
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: u64) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        admin::propose(&env, admin, new_admin, expires_at)
+    }
+
+    /// Accept a pending admin proposal (proposed admin only)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let new_admin = admin::accept(&env)?;
+        env.storage().instance().set(&ADMIN, &new_admin);
         Ok(())
     }
 
+    /// Get the current admin
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ADMIN)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<PendingAdmin> {
+        admin::get_pending(&env)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::grant(&env, role, account, admin)
+    }
+
+    /// Revoke a role from an account (admin only)
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::revoke(&env, role, account, admin)
+    }
+
+    /// Check whether an account holds a role; the admin holds every role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        match env.storage().instance().get::<_, Address>(&ADMIN) {
+            Some(admin) => rbac::has_role(&env, &admin, role, &account),
+            None => false,
+        }
+    }
+
+    /// Replace this contract's code with an uploaded wasm (admin only)
+    ///
+    /// Storage is kept; call `migrate` afterwards to convert it if the new
+    /// code changed its layout.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::upgrade(&env, new_wasm_hash, admin);
+        Ok(())
+    }
+
+    /// Schema version of the data in storage
+    pub fn version(env: Env) -> u32 {
+        upgrade::stored_version(&env)
+    }
+
+    /// Convert stored data to this code's schema version (admin only)
+    ///
+    /// Returns the version now in storage.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::migrate(&env, SCHEMA_VERSION, Self::migrate_step, admin)
+    }
+
+    /// Set how far and how often storage lifetimes are extended (admin only)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        ttl::set_config(&env, config, admin)
+    }
+
+    /// Get the storage lifetime settings
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl::get_config(&env)
+    }
+
     /// Check if NGO is verified
     pub fn is_verified(env: Env, ngo_address: Address) -> bool {
+        ttl::extend_instance(&env);
+
         let ngos: Map<Address, NGOInfo> = match env.storage().instance().get(&NGOS) {
             Some(n) => n,
             None => return false,
@@ -277,6 +427,9 @@ impl NGORegistry {
 
     /// Increment campaign count for NGO
     pub fn increment_campaign_count(env: Env, ngo_address: Address) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Campaigns)?;
+        ttl::extend_instance(&env);
+
         let mut ngos: Map<Address, NGOInfo> = env.storage()
             .instance()
             .get(&NGOS)
@@ -286,9 +439,13 @@ impl NGORegistry {
             .ok_or(Error::NGONotRegistered)?;
 
         ngo_info.total_campaigns += 1;
-        ngos.set(ngo_address, ngo_info);
+        let total_campaigns = ngo_info.total_campaigns;
+        ngos.set(ngo_address.clone(), ngo_info);
         env.storage().instance().set(&NGOS, &ngos);
 
+        event::emit_ngo_campaign_counted(&env, ngo_address.clone(), total_campaigns);
+        event::emit_audit_trail(&env, event::NGO_CAMPAIGN_COUNTED, ngo_address);
+
         Ok(())
     }
 
@@ -322,3 +479,229 @@ impl NGORegistry {
         result
     }
 }
+
+impl NGORegistry {
+    /// Rewrite entries stored under schema `from` in the layout of `from + 1`
+    ///
+    /// Version 2 indexes NGOs by normalized registration number. NGOs
+    /// registered before the index are added to it; where two share a
+    /// number, the first keeps the index entry.
+    fn migrate_step(env: &Env, from: u32) {
+        if from != 1 {
+            return;
+        }
+
+        let mut ngos: Map<Address, NGOInfo> = env.storage()
+            .instance()
+            .get(&NGOS)
+            .unwrap_or(Map::new(env));
+        for (address, mut info) in ngos.iter() {
+            let registration_number = match Self::normalize_registration_number(env, &info.registration_number) {
+                Some(number) => number,
+                None => continue,
+            };
+            let reg_key = DataKey::RegistrationNumber(registration_number.clone());
+            if !env.storage().persistent().has(&reg_key) {
+                env.storage().persistent().set(&reg_key, &address);
+                ttl::extend_persistent(env, &reg_key);
+            }
+            info.registration_number = registration_number;
+            ngos.set(address, info);
+        }
+        env.storage().instance().set(&NGOS, &ngos);
+    }
+
+    /// Trim surrounding whitespace and upper-case a registration number
+    ///
+    /// Returns `None` if nothing is left or the number is too long.
+    fn normalize_registration_number(env: &Env, registration_number: &String) -> Option<String> {
+        let len = registration_number.len();
+        if len > MAX_REGISTRATION_NUMBER_LEN {
+            return None;
+        }
+        let mut buf = [0u8; MAX_REGISTRATION_NUMBER_LEN as usize];
+        let bytes = &mut buf[..len as usize];
+        registration_number.copy_into_slice(bytes);
+        bytes.make_ascii_uppercase();
+
+        let start = bytes.iter().position(|b| !b.is_ascii_whitespace())?;
+        let end = bytes.iter().rposition(|b| !b.is_ascii_whitespace())? + 1;
+        Some(String::from_bytes(env, &bytes[start..end]))
+    }
+
+    /// Require the stored admin's authorization
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Require the caller's authorization and one of `roles`
+    fn require_role(env: &Env, caller: &Address, roles: &[Role]) -> Result<(), Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        rbac::require_role(env, &admin, caller, roles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Events};
+    use soroban_sdk::IntoVal;
+
+    fn setup(env: &Env) -> NGORegistryClient<'_> {
+        env.mock_all_auths();
+
+        let registry_id = env.register_contract(None, NGORegistry);
+        let registry = NGORegistryClient::new(env, &registry_id);
+        registry.initialize(&Address::generate(env));
+        registry
+    }
+
+    #[test]
+    fn test_self_registration_registers_caller() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let country = String::from_str(&env, "NP");
+
+        registry.register_ngo(&first, &String::from_str(&env, "Relief One"), &String::from_str(&env, "REG-1"), &country);
+        registry.register_ngo(&second, &String::from_str(&env, "Relief Two"), &String::from_str(&env, "REG-2"), &country);
+
+        assert_eq!(registry.get_ngo_info(&first).unwrap().address, first);
+        assert_eq!(registry.get_ngo_info(&second).unwrap().address, second);
+        assert!(registry.get_ngo_info(&registry.address).is_none());
+        assert_eq!(registry.get_ngo_by_registration(&String::from_str(&env, "REG-2")), Some(second));
+    }
+
+    #[test]
+    fn test_duplicate_registration_number_rejected() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let reg = String::from_str(&env, "REG-1");
+        let country = String::from_str(&env, "NP");
+
+        registry.register_ngo(&first, &String::from_str(&env, "Relief"), &reg, &country);
+        assert_eq!(
+            registry.try_register_ngo(&second, &String::from_str(&env, "Relief"), &reg, &country),
+            Err(Ok(Error::DuplicateRegistration))
+        );
+        assert_eq!(
+            registry.try_register_ngo(&second, &String::from_str(&env, "Relief"), &String::from_str(&env, " reg-1 "), &country),
+            Err(Ok(Error::DuplicateRegistration))
+        );
+        assert_eq!(
+            registry.try_register_ngo(&second, &String::from_str(&env, "Relief"), &String::from_str(&env, "  "), &country),
+            Err(Ok(Error::InvalidRegistrationNumber))
+        );
+        assert_eq!(
+            registry.try_register_ngo(&first, &String::from_str(&env, "Relief"), &String::from_str(&env, "REG-9"), &country),
+            Err(Ok(Error::NgoAlreadyRegistered))
+        );
+        assert_eq!(registry.get_ngo_by_registration(&String::from_str(&env, "reg-1")), Some(first));
+    }
+
+    #[test]
+    fn test_migrate_indexes_existing_registrations() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let existing = Address::generate(&env);
+        let newcomer = Address::generate(&env);
+        let country = String::from_str(&env, "NP");
+
+        // Simulate an NGO registered before registration numbers were indexed
+        env.as_contract(&registry.address, || {
+            let mut ngos: Map<Address, NGOInfo> = Map::new(&env);
+            ngos.set(existing.clone(), NGOInfo {
+                address: existing.clone(),
+                name: String::from_str(&env, "Relief"),
+                registration_number: String::from_str(&env, "reg-1"),
+                country: country.clone(),
+                status: NGOStatus::Verified,
+                registered_at: 0,
+                verified_at: 0,
+                total_campaigns: 3,
+            });
+            env.storage().instance().set(&NGOS, &ngos);
+            upgrade::set_version(&env, 1);
+        });
+
+        assert_eq!(registry.migrate(), 2);
+        assert_eq!(registry.get_ngo_by_registration(&String::from_str(&env, "REG-1")), Some(existing.clone()));
+        assert_eq!(registry.get_ngo_info(&existing).unwrap().registration_number, String::from_str(&env, "REG-1"));
+        assert_eq!(
+            registry.try_register_ngo(&newcomer, &String::from_str(&env, "Relief"), &String::from_str(&env, "REG-1"), &country),
+            Err(Ok(Error::DuplicateRegistration))
+        );
+    }
+
+    #[test]
+    fn test_revocation_emits_event_and_audit_trail() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let ngo = Address::generate(&env);
+        let admin = env.as_contract(&registry.address, || {
+            env.storage().instance().get::<_, Address>(&ADMIN).unwrap()
+        });
+        registry.register_ngo(&ngo, &String::from_str(&env, "Relief"), &String::from_str(&env, "REG-1"), &String::from_str(&env, "NP"));
+
+        registry.revoke_ngo(&admin, &ngo);
+        let events = env.events().all();
+        let len = events.len();
+        let no_campaign = String::from_str(&env, "");
+        let (_, topics, data) = events.get(len - 2).unwrap();
+        assert_eq!(topics, (event::NGO_REVOKED, no_campaign.clone(), ngo.clone()).into_val(&env));
+        let (version, revoked): (u32, event::NGORevokedEvent) = data.into_val(&env);
+        assert_eq!(version, event::EVENT_SCHEMA_VERSION);
+        assert_eq!(revoked.revoked_by, admin);
+        let (_, topics, data) = events.get(len - 1).unwrap();
+        assert_eq!(topics, (event::AUDIT_TRAIL, no_campaign, admin.clone()).into_val(&env));
+        let (_, audit): (u32, event::AuditTrailEvent) = data.into_val(&env);
+        assert_eq!(audit.event_type, event::NGO_REVOKED);
+    }
+
+    #[test]
+    fn test_schema_version_and_migrate() {
+        let env = Env::default();
+        let registry = setup(&env);
+        assert_eq!(registry.version(), SCHEMA_VERSION);
+        assert_eq!(registry.migrate(), SCHEMA_VERSION);
+
+        // Storage written by newer code cannot be migrated down
+        env.as_contract(&registry.address, || upgrade::set_version(&env, SCHEMA_VERSION + 1));
+        assert_eq!(registry.try_migrate(), Err(Ok(Error::UnsupportedSchemaVersion)));
+    }
+
+    #[test]
+    fn test_verifier_role_delegates_verification() {
+        let env = Env::default();
+        let registry = setup(&env);
+        let verifier = Address::generate(&env);
+        let ngo = Address::generate(&env);
+        registry.register_ngo(&ngo, &String::from_str(&env, "Relief"), &String::from_str(&env, "REG-1"), &String::from_str(&env, "NP"));
+
+        assert_eq!(registry.try_verify_ngo(&verifier, &ngo), Err(Ok(Error::Unauthorized)));
+
+        registry.grant_role(&Role::Verifier, &verifier);
+        assert!(registry.has_role(&Role::Verifier, &verifier));
+        assert!(!registry.has_role(&Role::Operator, &verifier));
+        registry.verify_ngo(&verifier, &ngo);
+        assert!(registry.is_verified(&ngo));
+
+        // Verifiers cannot pause, and the admin role cannot be handed out
+        assert_eq!(registry.try_pause(&verifier, &PauseScope::All), Err(Ok(Error::Unauthorized)));
+        assert_eq!(registry.try_grant_role(&Role::Admin, &verifier), Err(Ok(Error::InvalidRole)));
+
+        registry.revoke_role(&Role::Verifier, &verifier);
+        assert_eq!(registry.try_suspend_ngo(&verifier, &ngo), Err(Ok(Error::Unauthorized)));
+    }
+}
@@ -0,0 +1,48 @@
+use soroban_sdk::{contracttype, Address, Env};
+use crate::error::Error;
+use crate::event;
+use crate::types::{Operation, PauseScope};
+
+// Circuit breaker shared by every Relifo contract
+//
+// An operator pauses a whole contract or one operation. Queries and
+// entrypoints that only unwind funds or access, such as refunds,
+// revocations and authorization cancellations, keep working while paused.
+
+/// Pause flags, kept in instance storage
+#[contracttype]
+#[derive(Clone)]
+enum PauseKey {
+    Paused(PauseScope),
+}
+
+/// Pause or unpause a scope
+///
+/// The caller must already have checked that `changed_by` may do so.
+pub fn set_paused(env: &Env, scope: PauseScope, paused: bool, changed_by: Address) {
+    let key = PauseKey::Paused(scope);
+    if paused {
+        env.storage().instance().set(&key, &true);
+        event::emit_contract_paused(env, scope, changed_by.clone());
+        event::emit_audit_trail(env, event::CONTRACT_PAUSED, changed_by);
+    } else {
+        env.storage().instance().remove(&key);
+        event::emit_contract_unpaused(env, scope, changed_by.clone());
+        event::emit_audit_trail(env, event::CONTRACT_UNPAUSED, changed_by);
+    }
+}
+
+/// Whether the contract is paused entirely or, for `Only`, for that operation
+pub fn is_paused(env: &Env, scope: PauseScope) -> bool {
+    let storage = env.storage().instance();
+    storage.has(&PauseKey::Paused(PauseScope::All))
+        || (scope != PauseScope::All && storage.has(&PauseKey::Paused(scope)))
+}
+
+/// Fail with `ContractPaused` if the operation may not run
+pub fn require_not_paused(env: &Env, operation: Operation) -> Result<(), Error> {
+    if is_paused(env, PauseScope::Only(operation)) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
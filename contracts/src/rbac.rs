@@ -0,0 +1,75 @@
+use soroban_sdk::{contracttype, Address, Env};
+use crate::error::Error;
+use crate::event;
+use crate::ttl;
+use crate::types::Role;
+
+// Role-based access control shared by every Relifo contract
+//
+// Each contract keeps its own role grants, so a key can verify NGOs
+// without being able to approve merchants. The contract admin holds every
+// role without a grant.
+
+/// Role grants, one persistent entry per role and account
+#[contracttype]
+#[derive(Clone)]
+enum RoleKey {
+    Member(Role, Address),
+}
+
+/// Whether `account` holds `role`, given the contract's admin
+pub fn has_role(env: &Env, admin: &Address, role: Role, account: &Address) -> bool {
+    account == admin
+        || (role != Role::Admin
+            && env.storage().persistent().has(&RoleKey::Member(role, account.clone())))
+}
+
+/// Require the caller's authorization and fail with `Unauthorized` unless it holds one of `roles`
+///
+/// A grant that is used has its lifetime extended.
+pub fn require_role(env: &Env, admin: &Address, caller: &Address, roles: &[Role]) -> Result<(), Error> {
+    caller.require_auth();
+    if caller == admin {
+        return Ok(());
+    }
+    for role in roles {
+        if *role != Role::Admin && ttl::extend_persistent(env, &RoleKey::Member(*role, caller.clone())) {
+            return Ok(());
+        }
+    }
+    Err(Error::Unauthorized)
+}
+
+/// Grant `role` to `account`
+///
+/// The caller must already have checked the admin's authorization.
+pub fn grant(env: &Env, role: Role, account: Address, admin: Address) -> Result<(), Error> {
+    if role == Role::Admin {
+        return Err(Error::InvalidRole);
+    }
+
+    let key = RoleKey::Member(role, account.clone());
+    env.storage().persistent().set(&key, &true);
+    ttl::extend_persistent(env, &key);
+
+    event::emit_role_granted(env, role, account, admin.clone());
+    event::emit_audit_trail(env, event::ROLE_GRANTED, admin);
+
+    Ok(())
+}
+
+/// Revoke `role` from `account`
+///
+/// The caller must already have checked the admin's authorization.
+pub fn revoke(env: &Env, role: Role, account: Address, admin: Address) -> Result<(), Error> {
+    if role == Role::Admin {
+        return Err(Error::InvalidRole);
+    }
+
+    env.storage().persistent().remove(&RoleKey::Member(role, account.clone()));
+
+    event::emit_role_revoked(env, role, account, admin.clone());
+    event::emit_audit_trail(env, event::ROLE_REVOKED, admin);
+
+    Ok(())
+}
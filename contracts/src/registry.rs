@@ -0,0 +1,57 @@
+use soroban_sdk::{contractclient, Address, Env, String, Vec};
+use crate::error::Error;
+use crate::types::Category;
+
+// Cross-contract interfaces of the registries the Relifo contracts rely on.
+// Only the entrypoints other contracts call are declared here; the generated
+// clients invoke the deployed registry contracts by address.
+
+/// ReliefVault interface
+#[contractclient(name = "VaultClient")]
+pub trait VaultInterface {
+    /// Get the NGO that owns a campaign
+    fn get_campaign_ngo(env: Env, campaign_id: String) -> Option<Address>;
+}
+
+/// NGO Registry interface
+#[contractclient(name = "NGORegistryClient")]
+pub trait NGORegistryInterface {
+    /// Check if NGO is verified
+    fn is_verified(env: Env, ngo_address: Address) -> bool;
+}
+
+/// Beneficiary Registry interface
+#[contractclient(name = "BeneficiaryRegistryClient")]
+pub trait BeneficiaryRegistryInterface {
+    /// Check if beneficiary is approved for a campaign
+    fn is_approved_for_campaign(env: Env, beneficiary: Address, campaign_id: String) -> bool;
+
+    /// Update a beneficiary's category spending in a campaign (called after transaction)
+    fn update_spending(
+        env: Env,
+        beneficiary: Address,
+        campaign_id: String,
+        category: Category,
+        amount: i128,
+    ) -> Result<(), Error>;
+}
+
+/// Merchant Registry interface
+#[contractclient(name = "MerchantRegistryClient")]
+pub trait MerchantRegistryInterface {
+    /// Check if merchant is approved for category
+    fn is_approved_for_category(env: Env, merchant_address: Address, category: Category) -> bool;
+
+    /// Update merchant received amount (called after transaction)
+    fn update_received(env: Env, merchant_address: Address, amount: i128) -> Result<(), Error>;
+}
+
+/// Category Registry interface
+#[contractclient(name = "CategoryRegistryClient")]
+pub trait CategoryRegistryInterface {
+    /// Check a category and its ancestors are defined and active
+    fn is_active(env: Env, category: Category) -> bool;
+
+    /// Get a category followed by its ancestors (empty if undefined)
+    fn get_path(env: Env, category: Category) -> Vec<Category>;
+}
@@ -0,0 +1,49 @@
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use crate::types::Category;
+
+/// Stand-in category registry for unit tests
+///
+/// Every category is active unless retired, and has no parent unless
+/// one is set.
+#[contract]
+pub struct MockCategories;
+
+#[contractimpl]
+impl MockCategories {
+    pub fn set_parent(env: Env, category: Category, parent: Category) {
+        env.storage().instance().set(&category, &parent);
+    }
+
+    pub fn retire(env: Env, category: Category) {
+        env.storage().instance().set(&(category, true), &true);
+    }
+
+    pub fn is_active(env: Env, category: Category) -> bool {
+        !env.storage().instance().has(&(category, true))
+    }
+
+    pub fn get_path(env: Env, category: Category) -> Vec<Category> {
+        let mut path = Vec::from_array(&env, [category.clone()]);
+        let mut current = category;
+        while let Some(parent) = env.storage().instance().get::<_, Category>(&current) {
+            path.push_back(parent.clone());
+            current = parent;
+        }
+        path
+    }
+}
+
+/// Stand-in ReliefVault for unit tests that only records campaign owners
+#[contract]
+pub struct MockVault;
+
+#[contractimpl]
+impl MockVault {
+    pub fn set_campaign_ngo(env: Env, campaign_id: String, ngo: Address) {
+        env.storage().instance().set(&campaign_id, &ngo);
+    }
+
+    pub fn get_campaign_ngo(env: Env, campaign_id: String) -> Option<Address> {
+        env.storage().instance().get(&campaign_id)
+    }
+}
@@ -4,7 +4,6 @@ use crate::error::Error;
 
 /// USDC token client wrapper for interacting with USDC contract on Stellar
 /// This provides a convenient interface for all token operations in the Relifo system
-
 pub struct TokenClient<'a> {
     env: &'a Env,
     address: Address,
@@ -0,0 +1,102 @@
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Val};
+use crate::error::Error;
+use crate::event;
+
+// Storage lifetimes shared by every Relifo contract
+//
+// Soroban archives entries whose TTL runs out. Every state-changing call
+// extends the contract instance, which holds the registries' maps and
+// config, and each persistent entry is extended when it is written. The
+// checks the vault makes on every donation and payout extend what they
+// read as well, and the `bump_*` entrypoints let anyone keep a record
+// alive between uses.
+
+/// Ledgers closed per day at roughly five seconds per ledger
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// When and how far entries are extended, in ledgers
+///
+/// An entry whose TTL has dropped below `*_threshold` is extended to
+/// `*_extend_to`; entries above the threshold are left alone, so frequent
+/// access does not pay for an extension every time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
+}
+
+impl TtlConfig {
+    /// Instance lives at least a week and is topped up to 30 days;
+    /// persistent records live at least 30 days and are topped up to 120
+    pub fn default_config() -> Self {
+        TtlConfig {
+            instance_threshold: 7 * DAY_IN_LEDGERS,
+            instance_extend_to: 30 * DAY_IN_LEDGERS,
+            persistent_threshold: 30 * DAY_IN_LEDGERS,
+            persistent_extend_to: 120 * DAY_IN_LEDGERS,
+        }
+    }
+}
+
+/// TTL settings, kept in instance storage
+#[contracttype]
+#[derive(Clone)]
+enum TtlKey {
+    Config,
+}
+
+/// Get the TTL settings, falling back to the defaults
+pub fn get_config(env: &Env) -> TtlConfig {
+    env.storage()
+        .instance()
+        .get(&TtlKey::Config)
+        .unwrap_or_else(TtlConfig::default_config)
+}
+
+/// Replace the TTL settings
+///
+/// The caller must already have checked the admin's authorization.
+pub fn set_config(env: &Env, config: TtlConfig, admin: Address) -> Result<(), Error> {
+    let max_ttl = env.storage().max_ttl();
+    if config.instance_threshold > config.instance_extend_to
+        || config.persistent_threshold > config.persistent_extend_to
+        || config.instance_extend_to == 0
+        || config.persistent_extend_to == 0
+        || config.instance_extend_to > max_ttl
+        || config.persistent_extend_to > max_ttl
+    {
+        return Err(Error::InvalidTtlConfig);
+    }
+
+    env.storage().instance().set(&TtlKey::Config, &config);
+    extend_instance(env);
+
+    event::emit_ttl_config_updated(env, config, admin.clone());
+    event::emit_audit_trail(env, event::TTL_CONFIG_UPDATED, admin);
+
+    Ok(())
+}
+
+/// Extend the contract instance and everything stored in it
+pub fn extend_instance(env: &Env) {
+    let config = get_config(env);
+    env.storage()
+        .instance()
+        .extend_ttl(config.instance_threshold, config.instance_extend_to);
+}
+
+/// Extend a persistent entry if it exists
+///
+/// Returns whether the entry was found.
+pub fn extend_persistent<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> bool {
+    let storage = env.storage().persistent();
+    if !storage.has(key) {
+        return false;
+    }
+    let config = get_config(env);
+    storage.extend_ttl(key, config.persistent_threshold, config.persistent_extend_to);
+    true
+}
@@ -0,0 +1,185 @@
+use soroban_sdk::{contracttype, Env, String, Symbol, SymbolStr, TryFromVal, Vec};
+use crate::error::Error;
+
+// Shared types used across Relifo contracts and their events
+
+/// Campaign lifecycle status
+///
+/// Active campaigns accept donations and allocations. Paused campaigns
+/// freeze all fund movement until resumed. Closed campaigns stop
+/// fundraising and allocating but let existing allocations be spent.
+/// Cancelled campaigns stop all spending.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum CampaignStatus {
+    Active = 0,
+    Paused = 1,
+    Closed = 2,
+    Cancelled = 3,
+}
+
+/// How beneficiaries receive funds
+///
+/// Direct payouts go straight to the beneficiary's wallet. Controlled
+/// funds are spent through authorized merchants within category limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum ControlMode {
+    Direct = 0,
+    Controlled = 1,
+}
+
+/// Longest category name accepted, in bytes
+const MAX_CATEGORY_LEN: u32 = 32;
+
+/// Spending category, such as `food`, `medicine` or `infant_formula`
+///
+/// Names are lower-case ASCII letters, digits and underscores. Build one
+/// with `Category::new`, and pass categories received from callers through
+/// `normalize`, which lower-cases them so `Food` and `food` are the same
+/// category. The vault, the registries and events all key limits and
+/// approvals by the normalized value.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[contracttype]
+pub struct Category(String);
+
+impl Category {
+    /// Build a category from its name
+    pub fn new(env: &Env, name: &str) -> Result<Category, Error> {
+        Category(String::from_str(env, name)).normalize(env)
+    }
+
+    /// Build a category from a name stored as a string, as the vault did
+    /// before schema version 2
+    pub fn from_string(env: &Env, name: &String) -> Result<Category, Error> {
+        Category(name.clone()).normalize(env)
+    }
+
+    /// Build a category from a name stored as a symbol, as the registries
+    /// did before schema version 2
+    pub fn from_symbol(env: &Env, name: &Symbol) -> Result<Category, Error> {
+        let name = SymbolStr::try_from_val(env, &name.to_symbol_val()).map_err(|_| Error::InvalidCategory)?;
+        let name: &[u8] = name.as_ref();
+        Category(String::from_bytes(env, name)).normalize(env)
+    }
+
+    /// The category's name
+    pub fn name(&self) -> &String {
+        &self.0
+    }
+
+    /// Lower-case the name and check it is a valid category name
+    pub fn normalize(&self, env: &Env) -> Result<Category, Error> {
+        let len = self.0.len();
+        if len == 0 || len > MAX_CATEGORY_LEN {
+            return Err(Error::InvalidCategory);
+        }
+        let mut buf = [0u8; MAX_CATEGORY_LEN as usize];
+        let name = &mut buf[..len as usize];
+        self.0.copy_into_slice(name);
+        name.make_ascii_lowercase();
+
+        if !name.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'_') {
+            return Err(Error::InvalidCategory);
+        }
+        Ok(Category(String::from_bytes(env, name)))
+    }
+
+    /// Normalize every category in a list
+    pub fn normalize_all(env: &Env, categories: &Vec<Category>) -> Result<Vec<Category>, Error> {
+        let mut normalized = Vec::new(env);
+        for category in categories.iter() {
+            normalized.push_back(category.normalize(env)?);
+        }
+        Ok(normalized)
+    }
+}
+
+#[cfg(test)]
+impl Category {
+    /// A category exactly as a caller might send it, before normalization
+    pub fn unchecked(env: &Env, name: &str) -> Category {
+        Category(String::from_str(env, name))
+    }
+}
+
+/// Position of a category in the taxonomy
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ParentCategory {
+    /// Top-level category
+    TopLevel,
+    /// Child of the given category
+    Under(Category),
+}
+
+impl ParentCategory {
+    /// The parent category, if any
+    pub fn category(&self) -> Option<Category> {
+        match self {
+            ParentCategory::TopLevel => None,
+            ParentCategory::Under(parent) => Some(parent.clone()),
+        }
+    }
+}
+
+impl From<Option<Category>> for ParentCategory {
+    fn from(parent: Option<Category>) -> Self {
+        match parent {
+            Some(parent) => ParentCategory::Under(parent),
+            None => ParentCategory::TopLevel,
+        }
+    }
+}
+
+/// Group of state-changing entrypoints that can be paused on its own
+///
+/// Each contract gates the groups it implements; pausing a group a
+/// contract does not implement has no effect there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum Operation {
+    /// Creating, configuring and changing the status of campaigns
+    Campaigns = 0,
+    /// Donations to campaigns
+    Donations = 1,
+    /// Allocations and direct transfers to beneficiaries
+    Allocations = 2,
+    /// Authorizing, executing and recording merchant spending, and direct transfers
+    Spending = 3,
+    /// Registering and approving NGOs, beneficiaries, merchants and categories
+    Registrations = 4,
+}
+
+/// What an emergency pause applies to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum PauseScope {
+    /// Every state-changing operation of the contract
+    All,
+    /// A single operation
+    Only(Operation),
+}
+
+/// Role that can be granted to delegate part of a contract's administration
+///
+/// The admin holds every role. Other roles are granted per contract by
+/// its admin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum Role {
+    /// The contract admin; changed only through `propose_admin`
+    Admin = 0,
+    /// Verifies NGOs and approves merchants
+    Verifier = 1,
+    /// May pause a contract on finding irregularities
+    Auditor = 2,
+    /// Runs operational tasks such as taxonomy upkeep, migrations and pauses
+    Operator = 3,
+    /// Approves beneficiaries and manages their limits
+    FieldAgent = 4,
+}
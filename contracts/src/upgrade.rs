@@ -0,0 +1,69 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env};
+use crate::error::Error;
+use crate::event;
+
+// Code upgrades and storage schema versions shared by every Relifo contract
+//
+// `upgrade` swaps the contract's code but keeps its storage. The new code
+// then runs `migrate`, which rewrites entries stored under older layouts
+// in place, one schema version at a time.
+
+/// Upgrade flags, kept in instance storage
+#[contracttype]
+#[derive(Clone)]
+enum UpgradeKey {
+    SchemaVersion,
+}
+
+/// Schema version of the data in storage
+///
+/// Contracts initialized before versioning are at version 1.
+pub fn stored_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&UpgradeKey::SchemaVersion)
+        .unwrap_or(1)
+}
+
+/// Whether a schema version has been recorded in storage
+pub fn has_version(env: &Env) -> bool {
+    env.storage().instance().has(&UpgradeKey::SchemaVersion)
+}
+
+/// Record the schema version of freshly initialized storage
+pub fn set_version(env: &Env, version: u32) {
+    env.storage().instance().set(&UpgradeKey::SchemaVersion, &version);
+}
+
+/// Replace the contract's code with an uploaded wasm
+///
+/// The caller must already have checked the admin's authorization.
+pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>, admin: Address) {
+    env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+    event::emit_contract_upgraded(env, new_wasm_hash, admin.clone());
+    event::emit_audit_trail(env, event::CONTRACT_UPGRADED, admin);
+}
+
+/// Bring storage up to `target`, calling `step(env, v)` to move from v to v + 1
+///
+/// Returns the schema version now in storage. Running it again once
+/// storage is current does nothing.
+pub fn migrate(env: &Env, target: u32, step: fn(&Env, u32), admin: Address) -> Result<u32, Error> {
+    let from = stored_version(env);
+    if from > target {
+        return Err(Error::UnsupportedSchemaVersion);
+    }
+    if from == target {
+        return Ok(target);
+    }
+
+    for version in from..target {
+        step(env, version);
+    }
+    set_version(env, target);
+
+    event::emit_schema_migrated(env, from, target, admin.clone());
+    event::emit_audit_trail(env, event::SCHEMA_MIGRATED, admin);
+
+    Ok(target)
+}
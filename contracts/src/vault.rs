@@ -1,30 +1,121 @@
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, String, Symbol, Vec};
+use crate::admin::{self, PendingAdmin};
 use crate::error::Error;
 use crate::event;
+use crate::pause;
+use crate::rbac;
+use crate::registry::{BeneficiaryRegistryClient, CategoryRegistryClient, MerchantRegistryClient, NGORegistryClient};
 use crate::token::TokenClient;
+use crate::ttl::{self, TtlConfig};
+use crate::types::{CampaignStatus, Category, ControlMode, Operation, PauseScope, Role};
+use crate::upgrade;
+
+/// How long a spending authorization stays executable (7 days)
+const AUTH_VALIDITY_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Storage layout version written by this code
+const SCHEMA_VERSION: u32 = 2;
+
+// Instance keys of schema version 1, which kept every campaign, balance,
+// allocation and authorization in one map each
+const LEGACY_ADMIN: Symbol = symbol_short!("ADMIN");
+const LEGACY_USDC_TOKEN: Symbol = symbol_short!("USDC");
+const LEGACY_CAMPAIGNS: Symbol = symbol_short!("CAMPAIGNS");
+const LEGACY_BALANCES: Symbol = symbol_short!("BALANCES");
+const LEGACY_ALLOCATIONS: Symbol = symbol_short!("ALLOCS");
+const LEGACY_AUTHS: Symbol = symbol_short!("AUTHS");
+const LEGACY_AUTH_COUNTER: Symbol = symbol_short!("AUTH_CNT");
+
+/// Storage keys
+///
+/// Contract configuration lives in instance storage. Campaigns, balances,
+/// allocations and authorizations each get their own persistent entry so
+/// that no single entry grows with the number of campaigns or beneficiaries.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    UsdcToken,
+    NgoRegistry,
+    BeneficiaryRegistry,
+    MerchantRegistry,
+    CategoryRegistry,
+    AuthCounter,
+    Campaign(String),
+    Balance(String),
+    Allocation(String, Address),
+    /// Campaigns in which the beneficiary holds an allocation
+    BenefCampaigns(Address),
+    Auth(u64),
+    Contribution(String, Address),
+}
 
-// Storage keys
-const ADMIN: Symbol = symbol_short!("ADMIN");
-const USDC_TOKEN: Symbol = symbol_short!("USDC");
-const CAMPAIGNS: Symbol = symbol_short!("CAMPAIGNS");
-const BALANCES: Symbol = symbol_short!("BALANCES");
-const ALLOCATIONS: Symbol = symbol_short!("ALLOCS");
-const AUTHS: Symbol = symbol_short!("AUTHS");
-const AUTH_COUNTER: Symbol = symbol_short!("AUTH_CNT");
+/// What happens once a campaign's donations reach its target
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum FundingPolicy {
+    AllowOverfunding = 0,
+    StopAtTarget = 1,
+}
 
 /// Campaign data structure
+///
+/// Deadlines are ledger timestamps; 0 means no deadline. When
+/// `beneficiary_cosign` is set, executing a spending authorization needs
+/// the beneficiary's signature as well as the merchant's.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Campaign {
     pub campaign_id: String,
     pub ngo_address: Address,
     pub target_amount: i128,
-    pub control_mode: String, // "DIRECT" or "CONTROLLED"
+    pub control_mode: ControlMode,
     pub created_at: u64,
-    pub status: String, // "ACTIVE", "PAUSED", "CLOSED"
+    pub status: CampaignStatus,
+    pub fundraising_deadline: u64,
+    pub spending_deadline: u64,
+    pub funding_policy: FundingPolicy,
+    pub beneficiary_cosign: bool,
+}
+
+/// Campaign fund accounting
+///
+/// `committed` is allocated to beneficiaries but not yet spent, `disbursed`
+/// has left the vault. `refund_pool` is set aside for donors once the
+/// campaign is closed or cancelled, and `refunded` is the part already
+/// claimed. Only the remainder is free for new allocations.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampaignBalance {
+    pub donated: i128,
+    pub committed: i128,
+    pub disbursed: i128,
+    pub refund_pool: i128,
+    pub refunded: i128,
+}
+
+impl CampaignBalance {
+    /// Funds neither allocated, disbursed nor set aside for refunds
+    pub fn available(&self) -> i128 {
+        self.donated - self.committed - self.disbursed - self.refund_pool
+    }
+}
+
+/// A donor's contribution to a campaign
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorContribution {
+    pub amount: i128,
+    pub refunded: i128,
 }
 
 /// Beneficiary allocation data
+///
+/// `reserved` and `category_reserved` hold amounts tied up in pending
+/// spending authorizations. Category spending and reservations are also
+/// counted against every ancestor category, so a limit on a parent covers
+/// its children.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BeneficiaryAllocation {
@@ -32,10 +123,23 @@ pub struct BeneficiaryAllocation {
     pub campaign_id: String,
     pub total_amount: i128,
     pub spent: i128,
-    pub control_mode: String,
-    pub categories: Vec<String>, // ["food", "medicine", "shelter"]
-    pub category_limits: Map<String, i128>,
-    pub category_spent: Map<String, i128>,
+    pub control_mode: ControlMode,
+    pub categories: Vec<Category>,
+    pub category_limits: Map<Category, i128>,
+    pub category_spent: Map<Category, i128>,
+    pub reserved: i128,
+    pub category_reserved: Map<Category, i128>,
+}
+
+/// Spending authorization status
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum AuthStatus {
+    Pending = 0,
+    Executed = 1,
+    Cancelled = 2,
+    Expired = 3,
 }
 
 /// Spending authorization data
@@ -43,12 +147,55 @@ pub struct BeneficiaryAllocation {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SpendingAuthorization {
     pub auth_id: u64,
+    pub campaign_id: String,
     pub beneficiary: Address,
     pub merchant: Address,
     pub amount: i128,
-    pub category: String,
+    pub category: Category,
+    /// The category followed by its ancestors in the taxonomy
+    pub category_path: Vec<Category>,
     pub created_at: u64,
-    pub status: String, // "PENDING", "EXECUTED", "CANCELLED"
+    pub expires_at: u64,
+    pub status: AuthStatus,
+}
+
+/// Campaign as stored under schema version 1
+#[contracttype]
+#[derive(Clone)]
+struct LegacyCampaign {
+    campaign_id: String,
+    ngo_address: Address,
+    target_amount: i128,
+    control_mode: String,
+    created_at: u64,
+    status: String,
+}
+
+/// Beneficiary allocation as stored under schema version 1
+#[contracttype]
+#[derive(Clone)]
+struct LegacyAllocation {
+    beneficiary: Address,
+    campaign_id: String,
+    total_amount: i128,
+    spent: i128,
+    control_mode: String,
+    categories: Vec<String>,
+    category_limits: Map<String, i128>,
+    category_spent: Map<String, i128>,
+}
+
+/// Spending authorization as stored under schema version 1
+#[contracttype]
+#[derive(Clone)]
+struct LegacyAuthorization {
+    auth_id: u64,
+    beneficiary: Address,
+    merchant: Address,
+    amount: i128,
+    category: String,
+    created_at: u64,
+    status: String,
 }
 
 /// ReliefVault Contract
@@ -58,66 +205,292 @@ pub struct ReliefVault;
 
 #[contractimpl]
 impl ReliefVault {
-    /// Initialize the vault contract
+    /// Initialize the vault contract with the USDC token and registry contracts
     pub fn initialize(
         env: Env,
         admin: Address,
         usdc_token: Address,
+        ngo_registry: Address,
+        beneficiary_registry: Address,
+        merchant_registry: Address,
+        category_registry: Address,
     ) -> Result<(), Error> {
         admin.require_auth();
 
-        // Check if already initialized
-        if env.storage().instance().has(&ADMIN) {
+        // Check if already initialized, including storage of version 1
+        // that has not been migrated yet
+        if env.storage().instance().has(&DataKey::Admin)
+            || env.storage().instance().has(&LEGACY_ADMIN)
+            || upgrade::has_version(&env)
+        {
             return Err(Error::AlreadyInitialized);
         }
 
         // Store admin and USDC token address
-        env.storage().instance().set(&ADMIN, &admin);
-        env.storage().instance().set(&USDC_TOKEN, &usdc_token);
-        
-        // Initialize empty maps
-        let campaigns: Map<String, Campaign> = Map::new(&env);
-        let balances: Map<String, i128> = Map::new(&env);
-        let allocations: Map<Address, BeneficiaryAllocation> = Map::new(&env);
-        let auths: Map<u64, SpendingAuthorization> = Map::new(&env);
-        
-        env.storage().instance().set(&CAMPAIGNS, &campaigns);
-        env.storage().instance().set(&BALANCES, &balances);
-        env.storage().instance().set(&ALLOCATIONS, &allocations);
-        env.storage().instance().set(&AUTHS, &auths);
-        env.storage().instance().set(&AUTH_COUNTER, &0u64);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::UsdcToken, &usdc_token);
+        env.storage().instance().set(&DataKey::AuthCounter, &0u64);
+
+        // Store registry addresses
+        env.storage().instance().set(&DataKey::NgoRegistry, &ngo_registry);
+        env.storage().instance().set(&DataKey::BeneficiaryRegistry, &beneficiary_registry);
+        env.storage().instance().set(&DataKey::MerchantRegistry, &merchant_registry);
+        env.storage().instance().set(&DataKey::CategoryRegistry, &category_registry);
+        upgrade::set_version(&env, SCHEMA_VERSION);
+        ttl::extend_instance(&env);
+
+        Ok(())
+    }
+
+    /// Pause every state-changing operation, or only one (operator, auditor or admin)
+    pub fn pause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator or auditor
+        Self::require_role(&env, &caller, &[Role::Operator, Role::Auditor])?;
+
+        pause::set_paused(&env, scope, true, caller);
+        Ok(())
+    }
+
+    /// Lift a pause set for the same scope (operator or admin)
+    ///
+    /// Unpausing one operation leaves a contract-wide pause in place.
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        // Caller must be an operator
+        Self::require_role(&env, &caller, &[Role::Operator])?;
+
+        pause::set_paused(&env, scope, false, caller);
+        Ok(())
+    }
+
+    /// Check whether an operation may not run because of a pause
+    ///
+    /// `All` reports only a contract-wide pause.
+    pub fn is_paused(env: Env, scope: PauseScope) -> bool {
+        pause::is_paused(&env, scope)
+    }
+
+    /// Propose a new admin, who takes over once they accept (admin only)
+    ///
+    /// `expires_at` is the ledger timestamp after which the proposal can no
+    /// longer be accepted; 0 means it never lapses. A new proposal replaces
+    /// the previous one.
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: u64) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        admin::propose(&env, admin, new_admin, expires_at)
+    }
+
+    /// Accept a pending admin proposal (proposed admin only)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let new_admin = admin::accept(&env)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
+    }
+
+    /// Get the current admin
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+
+    /// Get the proposed admin awaiting acceptance
+    pub fn get_pending_admin(env: Env) -> Option<PendingAdmin> {
+        admin::get_pending(&env)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        rbac::grant(&env, role, account, admin)
+    }
+
+    /// Revoke a role from an account (admin only)
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
 
+        rbac::revoke(&env, role, account, admin)
+    }
+
+    /// Check whether an account holds a role; the admin holds every role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        match env.storage().instance().get::<_, Address>(&DataKey::Admin) {
+            Some(admin) => rbac::has_role(&env, &admin, role, &account),
+            None => false,
+        }
+    }
+
+    /// Replace this contract's code with an uploaded wasm (admin only)
+    ///
+    /// Storage is kept; call `migrate` afterwards to convert it if the new
+    /// code changed its layout.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        upgrade::upgrade(&env, new_wasm_hash, admin);
+        Ok(())
+    }
+
+    /// Schema version of the data in storage
+    pub fn version(env: Env) -> u32 {
+        upgrade::stored_version(&env)
+    }
+
+    /// Convert stored data to this code's schema version (admin only)
+    ///
+    /// Returns the version now in storage.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        ttl::extend_instance(&env);
+
+        // Version 1 kept the admin under its own key
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .or_else(|| env.storage().instance().get(&LEGACY_ADMIN))
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+
+        upgrade::migrate(&env, SCHEMA_VERSION, Self::migrate_step, admin)
+    }
+
+    /// Set the registry contracts the vault checks (admin only)
+    ///
+    /// Vaults migrated from schema version 1 have none until this is called.
+    pub fn set_registries(
+        env: Env,
+        ngo_registry: Address,
+        beneficiary_registry: Address,
+        merchant_registry: Address,
+        category_registry: Address,
+    ) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        env.storage().instance().set(&DataKey::NgoRegistry, &ngo_registry);
+        env.storage().instance().set(&DataKey::BeneficiaryRegistry, &beneficiary_registry);
+        env.storage().instance().set(&DataKey::MerchantRegistry, &merchant_registry);
+        env.storage().instance().set(&DataKey::CategoryRegistry, &category_registry);
+
+        event::emit_config_updated(&env, symbol_short!("ngo_reg"), ngo_registry, admin.clone());
+        event::emit_config_updated(&env, symbol_short!("benef_reg"), beneficiary_registry, admin.clone());
+        event::emit_config_updated(&env, symbol_short!("merch_reg"), merchant_registry, admin.clone());
+        event::emit_config_updated(&env, symbol_short!("cat_reg"), category_registry, admin.clone());
+        event::emit_audit_trail(&env, event::CONFIG_UPDATED, admin);
+
+        Ok(())
+    }
+
+    /// Set how far and how often storage lifetimes are extended (admin only)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let admin = Self::require_admin(&env)?;
+
+        ttl::set_config(&env, config, admin)
+    }
+
+    /// Get the storage lifetime settings
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl::get_config(&env)
+    }
+
+    /// Extend a campaign and its fund accounting (anyone)
+    pub fn bump_campaign(env: Env, campaign_id: String) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        if !ttl::extend_persistent(&env, &DataKey::Campaign(campaign_id.clone())) {
+            return Err(Error::CampaignNotFound);
+        }
+        ttl::extend_persistent(&env, &DataKey::Balance(campaign_id));
+        Ok(())
+    }
+
+    /// Extend a donor's contribution record in a campaign (anyone)
+    pub fn bump_contribution(env: Env, campaign_id: String, donor: Address) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        if !ttl::extend_persistent(&env, &DataKey::Contribution(campaign_id, donor)) {
+            return Err(Error::NothingToRefund);
+        }
         Ok(())
     }
 
+    /// Extend a beneficiary's campaign list and allocations (anyone)
+    ///
+    /// Returns the number of allocations extended.
+    pub fn bump_beneficiary(env: Env, beneficiary: Address) -> u32 {
+        ttl::extend_instance(&env);
+
+        let campaigns_key = DataKey::BenefCampaigns(beneficiary.clone());
+        if !ttl::extend_persistent(&env, &campaigns_key) {
+            return 0;
+        }
+        let campaign_ids: Vec<String> = env.storage()
+            .persistent()
+            .get(&campaigns_key)
+            .unwrap_or(Vec::new(&env));
+
+        let mut extended = 0;
+        for campaign_id in campaign_ids.iter() {
+            if ttl::extend_persistent(&env, &DataKey::Allocation(campaign_id, beneficiary.clone())) {
+                extended += 1;
+            }
+        }
+        extended
+    }
+
     /// Create a new relief campaign
     pub fn create_campaign(
         env: Env,
         ngo_address: Address,
         campaign_id: String,
         target_amount: i128,
-        control_mode: String,
+        control_mode: ControlMode,
+        fundraising_deadline: u64,
+        spending_deadline: u64,
+        funding_policy: FundingPolicy,
     ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Campaigns)?;
+        ttl::extend_instance(&env);
+
         ngo_address.require_auth();
 
         if target_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
 
-        // Validate control mode
-        if control_mode != String::from_str(&env, "DIRECT") 
-            && control_mode != String::from_str(&env, "CONTROLLED") {
-            return Err(Error::InvalidControlMode);
+        // Deadlines must lie in the future, and spending cannot end before fundraising
+        let now = env.ledger().timestamp();
+        if (fundraising_deadline != 0 && fundraising_deadline <= now)
+            || (spending_deadline != 0 && spending_deadline <= now)
+            || (spending_deadline != 0 && spending_deadline < fundraising_deadline)
+        {
+            return Err(Error::InvalidDeadline);
         }
 
-        // Get campaigns map
-        let mut campaigns: Map<String, Campaign> = env.storage()
-            .instance()
-            .get(&CAMPAIGNS)
-            .unwrap_or(Map::new(&env));
+        // Only verified NGOs may run campaigns
+        let ngo_registry: Address = Self::config_address(&env, DataKey::NgoRegistry)?;
+        if !NGORegistryClient::new(&env, &ngo_registry).is_verified(&ngo_address) {
+            return Err(Error::NGONotVerified);
+        }
 
         // Check if campaign already exists
-        if campaigns.contains_key(campaign_id.clone()) {
+        let campaign_key = DataKey::Campaign(campaign_id.clone());
+        if env.storage().persistent().has(&campaign_key) {
             return Err(Error::CampaignExists);
         }
 
@@ -126,22 +499,29 @@ impl ReliefVault {
             campaign_id: campaign_id.clone(),
             ngo_address: ngo_address.clone(),
             target_amount,
-            control_mode: control_mode.clone(),
-            created_at: env.ledger().timestamp(),
-            status: String::from_str(&env, "ACTIVE"),
+            control_mode,
+            created_at: now,
+            status: CampaignStatus::Active,
+            fundraising_deadline,
+            spending_deadline,
+            funding_policy,
+            beneficiary_cosign: false,
         };
 
         // Store campaign
-        campaigns.set(campaign_id.clone(), campaign);
-        env.storage().instance().set(&CAMPAIGNS, &campaigns);
+        env.storage().persistent().set(&campaign_key, &campaign);
+        ttl::extend_persistent(&env, &campaign_key);
 
         // Initialize campaign balance
-        let mut balances: Map<String, i128> = env.storage()
-            .instance()
-            .get(&BALANCES)
-            .unwrap_or(Map::new(&env));
-        balances.set(campaign_id.clone(), 0);
-        env.storage().instance().set(&BALANCES, &balances);
+        let balance = CampaignBalance {
+            donated: 0,
+            committed: 0,
+            disbursed: 0,
+            refund_pool: 0,
+            refunded: 0,
+        };
+        env.storage().persistent().set(&DataKey::Balance(campaign_id.clone()), &balance);
+        ttl::extend_persistent(&env, &DataKey::Balance(campaign_id.clone()));
 
         // Emit event
         event::emit_campaign_created(&env, campaign_id, ngo_address, target_amount, control_mode);
@@ -156,6 +536,9 @@ impl ReliefVault {
         campaign_id: String,
         amount: i128,
     ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Donations)?;
+        ttl::extend_instance(&env);
+
         donor.require_auth();
 
         if amount <= 0 {
@@ -163,21 +546,31 @@ impl ReliefVault {
         }
 
         // Get campaign
-        let campaigns: Map<String, Campaign> = env.storage()
-            .instance()
-            .get(&CAMPAIGNS)
-            .unwrap_or(Map::new(&env));
-        
-        let campaign = campaigns.get(campaign_id.clone())
+        let campaign = Self::load_campaign(&env, &campaign_id)?;
+
+        // Check campaign is accepting donations
+        if campaign.status != CampaignStatus::Active {
+            return Err(Error::CampaignNotActive);
+        }
+        if Self::deadline_passed(&env, campaign.fundraising_deadline) {
+            return Err(Error::FundraisingEnded);
+        }
+
+        let balance_key = DataKey::Balance(campaign_id.clone());
+        let mut balance: CampaignBalance = env.storage()
+            .persistent()
+            .get(&balance_key)
             .ok_or(Error::CampaignNotFound)?;
 
-        // Check campaign is active
-        if campaign.status != String::from_str(&env, "ACTIVE") {
-            return Err(Error::Unauthorized);
+        // Enforce the campaign's funding policy
+        if campaign.funding_policy == FundingPolicy::StopAtTarget
+            && balance.donated + amount > campaign.target_amount
+        {
+            return Err(Error::TargetReached);
         }
 
         // Get USDC token client
-        let usdc_address: Address = env.storage().instance().get(&USDC_TOKEN).unwrap();
+        let usdc_address: Address = Self::config_address(&env, DataKey::UsdcToken)?;
         let token = TokenClient::new(&env, &usdc_address);
 
         // Get vault address (this contract)
@@ -187,14 +580,19 @@ impl ReliefVault {
         token.transfer(&donor, &vault_address, amount)?;
 
         // Update campaign balance
-        let mut balances: Map<String, i128> = env.storage()
-            .instance()
-            .get(&BALANCES)
-            .unwrap_or(Map::new(&env));
-        
-        let current_balance = balances.get(campaign_id.clone()).unwrap_or(0);
-        balances.set(campaign_id.clone(), current_balance + amount);
-        env.storage().instance().set(&BALANCES, &balances);
+        balance.donated += amount;
+        env.storage().persistent().set(&balance_key, &balance);
+        ttl::extend_persistent(&env, &balance_key);
+
+        // Record the donor's contribution for refunds
+        let contribution_key = DataKey::Contribution(campaign_id.clone(), donor.clone());
+        let mut contribution: DonorContribution = env.storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(DonorContribution { amount: 0, refunded: 0 });
+        contribution.amount += amount;
+        env.storage().persistent().set(&contribution_key, &contribution);
+        ttl::extend_persistent(&env, &contribution_key);
 
         // Emit event
         event::emit_donation_received(&env, campaign_id, donor, amount);
@@ -209,10 +607,13 @@ impl ReliefVault {
         campaign_id: String,
         beneficiary_address: Address,
         amount: i128,
-        control_mode: String,
-        categories: Vec<String>,
-        category_limits: Map<String, i128>,
+        control_mode: ControlMode,
+        categories: Vec<Category>,
+        category_limits: Map<Category, i128>,
     ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Allocations)?;
+        ttl::extend_instance(&env);
+
         ngo_address.require_auth();
 
         if amount <= 0 {
@@ -220,49 +621,99 @@ impl ReliefVault {
         }
 
         // Get campaign
-        let campaigns: Map<String, Campaign> = env.storage()
-            .instance()
-            .get(&CAMPAIGNS)
-            .unwrap_or(Map::new(&env));
-        
-        let campaign = campaigns.get(campaign_id.clone())
-            .ok_or(Error::CampaignNotFound)?;
+        let campaign = Self::load_campaign(&env, &campaign_id)?;
 
         // Verify caller is NGO for this campaign
         if campaign.ngo_address != ngo_address {
             return Err(Error::Unauthorized);
         }
 
-        // Check campaign has sufficient funds
-        let balances: Map<String, i128> = env.storage()
-            .instance()
-            .get(&BALANCES)
-            .unwrap_or(Map::new(&env));
-        
-        let campaign_balance = balances.get(campaign_id.clone()).unwrap_or(0);
-        if campaign_balance < amount {
+        // Check campaign is accepting allocations
+        if campaign.status != CampaignStatus::Active {
+            return Err(Error::CampaignNotActive);
+        }
+        if Self::deadline_passed(&env, campaign.spending_deadline) {
+            return Err(Error::SpendingWindowClosed);
+        }
+
+        // Beneficiary must be approved for this campaign in the beneficiary registry
+        let beneficiary_registry: Address = Self::config_address(&env, DataKey::BeneficiaryRegistry)?;
+        if !BeneficiaryRegistryClient::new(&env, &beneficiary_registry)
+            .is_approved_for_campaign(&beneficiary_address, &campaign_id)
+        {
+            return Err(Error::BeneficiaryNotWhitelisted);
+        }
+
+        // Check campaign has sufficient free funds
+        let balance_key = DataKey::Balance(campaign_id.clone());
+        let mut balance: CampaignBalance = env.storage()
+            .persistent()
+            .get(&balance_key)
+            .ok_or(Error::CampaignNotFound)?;
+        if balance.available() < amount {
             return Err(Error::InsufficientBalance);
         }
 
-        // Create allocation
-        let mut allocations: Map<Address, BeneficiaryAllocation> = env.storage()
-            .instance()
-            .get(&ALLOCATIONS)
-            .unwrap_or(Map::new(&env));
+        // A beneficiary holds at most one allocation per campaign
+        let allocation_key = DataKey::Allocation(campaign_id.clone(), beneficiary_address.clone());
+        if env.storage().persistent().has(&allocation_key) {
+            return Err(Error::BeneficiaryExists);
+        }
+
+        // Categories and limits must be valid names, active in the taxonomy;
+        // each limit is positive and for one of the allocated categories
+        let categories = Category::normalize_all(&env, &categories)?;
+        let mut normalized_limits: Map<Category, i128> = Map::new(&env);
+        for (category, limit) in category_limits.iter() {
+            if limit <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            let category = category.normalize(&env)?;
+            if !categories.contains(&category) {
+                return Err(Error::InvalidCategory);
+            }
+            normalized_limits.set(category, limit);
+        }
+        let category_limits = normalized_limits;
+        let category_registry: Address = Self::config_address(&env, DataKey::CategoryRegistry)?;
+        let category_registry = CategoryRegistryClient::new(&env, &category_registry);
+        for category in categories.iter().chain(category_limits.keys().iter()) {
+            if !category_registry.is_active(&category) {
+                return Err(Error::InvalidCategory);
+            }
+        }
 
+        // Create allocation
         let allocation = BeneficiaryAllocation {
             beneficiary: beneficiary_address.clone(),
             campaign_id: campaign_id.clone(),
             total_amount: amount,
             spent: 0,
-            control_mode: control_mode.clone(),
+            control_mode,
             categories: categories.clone(),
             category_limits: category_limits.clone(),
             category_spent: Map::new(&env),
+            reserved: 0,
+            category_reserved: Map::new(&env),
         };
 
-        allocations.set(beneficiary_address.clone(), allocation);
-        env.storage().instance().set(&ALLOCATIONS, &allocations);
+        env.storage().persistent().set(&allocation_key, &allocation);
+        ttl::extend_persistent(&env, &allocation_key);
+
+        // Reserve the allocated funds
+        balance.committed += amount;
+        env.storage().persistent().set(&balance_key, &balance);
+        ttl::extend_persistent(&env, &balance_key);
+
+        // Index the campaign under the beneficiary
+        let campaigns_key = DataKey::BenefCampaigns(beneficiary_address.clone());
+        let mut benef_campaigns: Vec<String> = env.storage()
+            .persistent()
+            .get(&campaigns_key)
+            .unwrap_or(Vec::new(&env));
+        benef_campaigns.push_back(campaign_id.clone());
+        env.storage().persistent().set(&campaigns_key, &benef_campaigns);
+        ttl::extend_persistent(&env, &campaigns_key);
 
         // Emit event
         event::emit_funds_allocated(&env, campaign_id, beneficiary_address, amount);
@@ -270,102 +721,271 @@ impl ReliefVault {
         Ok(())
     }
 
-    /// Authorize spending (Controlled Mode)
+    /// Pay USDC straight to a beneficiary's wallet (Direct Mode)
+    ///
+    /// Draws from the beneficiary's DIRECT allocation in the campaign when
+    /// one exists, otherwise from the campaign's free balance, which is
+    /// only paid to beneficiaries approved for the campaign.
+    pub fn direct_transfer(
+        env: Env,
+        ngo_address: Address,
+        campaign_id: String,
+        beneficiary_address: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        // Pays out immediately, so it stops when either allocations or spending is paused
+        pause::require_not_paused(&env, Operation::Allocations)?;
+        pause::require_not_paused(&env, Operation::Spending)?;
+        ttl::extend_instance(&env);
+
+        ngo_address.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Get campaign
+        let campaign = Self::load_campaign(&env, &campaign_id)?;
+
+        // Verify caller is NGO for this campaign
+        if campaign.ngo_address != ngo_address {
+            return Err(Error::Unauthorized);
+        }
+
+        // Direct payouts are only allowed in DIRECT campaigns
+        if campaign.control_mode != ControlMode::Direct {
+            return Err(Error::InvalidControlMode);
+        }
+
+        // Closed campaigns may still pay out existing allocations
+        if campaign.status != CampaignStatus::Active && campaign.status != CampaignStatus::Closed {
+            return Err(Error::CampaignNotActive);
+        }
+        if Self::deadline_passed(&env, campaign.spending_deadline) {
+            return Err(Error::SpendingWindowClosed);
+        }
+
+        let balance_key = DataKey::Balance(campaign_id.clone());
+        let mut balance: CampaignBalance = env.storage()
+            .persistent()
+            .get(&balance_key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        // Pay from the beneficiary's allocation, or from free funds
+        let allocation_key = DataKey::Allocation(campaign_id.clone(), beneficiary_address.clone());
+        let allocation: Option<BeneficiaryAllocation> = env.storage().persistent().get(&allocation_key);
+        if let Some(mut allocation) = allocation {
+            if allocation.control_mode != ControlMode::Direct {
+                return Err(Error::InvalidControlMode);
+            }
+            if allocation.total_amount - allocation.spent < amount {
+                return Err(Error::InsufficientBalance);
+            }
+            allocation.spent += amount;
+            env.storage().persistent().set(&allocation_key, &allocation);
+            ttl::extend_persistent(&env, &allocation_key);
+            balance.committed -= amount;
+        } else {
+            if campaign.status != CampaignStatus::Active {
+                return Err(Error::CampaignNotActive);
+            }
+            if balance.available() < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            // Free funds go only to beneficiaries approved for this campaign
+            let beneficiary_registry: Address = Self::config_address(&env, DataKey::BeneficiaryRegistry)?;
+            if !BeneficiaryRegistryClient::new(&env, &beneficiary_registry)
+                .is_approved_for_campaign(&beneficiary_address, &campaign_id)
+            {
+                return Err(Error::BeneficiaryNotWhitelisted);
+            }
+        }
+
+        // Transfer USDC to beneficiary
+        let usdc_address: Address = Self::config_address(&env, DataKey::UsdcToken)?;
+        let token = TokenClient::new(&env, &usdc_address);
+        token.transfer(&env.current_contract_address(), &beneficiary_address, amount)?;
+
+        balance.disbursed += amount;
+        env.storage().persistent().set(&balance_key, &balance);
+        ttl::extend_persistent(&env, &balance_key);
+
+        // Emit event
+        event::emit_direct_transfer(&env, campaign_id, beneficiary_address, amount);
+
+        Ok(())
+    }
+
+    /// Authorize spending from the beneficiary's allocation in a campaign (Controlled Mode)
     pub fn authorize_spending(
         env: Env,
         beneficiary: Address,
+        campaign_id: String,
         merchant: Address,
         amount: i128,
-        category: String,
+        category: Category,
     ) -> Result<u64, Error> {
+        pause::require_not_paused(&env, Operation::Spending)?;
+        ttl::extend_instance(&env);
+
         beneficiary.require_auth();
+        let category = category.normalize(&env)?;
 
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
 
         // Get beneficiary allocation
-        let allocations: Map<Address, BeneficiaryAllocation> = env.storage()
-            .instance()
-            .get(&ALLOCATIONS)
-            .unwrap_or(Map::new(&env));
-        
-        let allocation = allocations.get(beneficiary.clone())
+        let allocation_key = DataKey::Allocation(campaign_id.clone(), beneficiary.clone());
+        let mut allocation: BeneficiaryAllocation = env.storage()
+            .persistent()
+            .get(&allocation_key)
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
+        // Existing allocations stay spendable until the campaign is paused or
+        // cancelled, or its spending deadline passes
+        let campaign = Self::require_spendable(&env, &campaign_id)?;
+        if Self::deadline_passed(&env, campaign.spending_deadline) {
+            return Err(Error::SpendingWindowClosed);
+        }
+
         // Check if controlled mode
-        if allocation.control_mode != String::from_str(&env, "CONTROLLED") {
+        if allocation.control_mode != ControlMode::Controlled {
             return Err(Error::InvalidControlMode);
         }
 
-        // Check beneficiary has sufficient balance
-        let remaining = allocation.total_amount - allocation.spent;
+        // The category, or one of its ancestors, must be allocated
+        let category_path = Self::category_path(&env, &category)?;
+        if !category_path.iter().any(|cat| allocation.categories.contains(&cat)) {
+            return Err(Error::InvalidCategory);
+        }
+
+        // Merchant must be approved for the category in the merchant registry
+        let merchant_registry: Address = Self::config_address(&env, DataKey::MerchantRegistry)?;
+        if !MerchantRegistryClient::new(&env, &merchant_registry).is_approved_for_category(&merchant, &category) {
+            return Err(Error::MerchantNotApproved);
+        }
+
+        // Check beneficiary has sufficient balance outside pending authorizations
+        let remaining = allocation.total_amount - allocation.spent - allocation.reserved;
         if remaining < amount {
             return Err(Error::InsufficientBalance);
         }
 
-        // Check category limit
-        if let Some(limit) = allocation.category_limits.get(category.clone()) {
-            let spent = allocation.category_spent.get(category.clone()).unwrap_or(0);
-            if spent + amount > limit {
-                return Err(Error::CategoryLimitExceeded);
+        // Check every limit covering the category, its own or an ancestor's
+        let mut limited = false;
+        for cat in category_path.iter() {
+            if let Some(limit) = allocation.category_limits.get(cat.clone()) {
+                let spent = allocation.category_spent.get(cat.clone()).unwrap_or(0);
+                let reserved = allocation.category_reserved.get(cat).unwrap_or(0);
+                if spent + reserved + amount > limit {
+                    return Err(Error::CategoryLimitExceeded);
+                }
+                limited = true;
             }
-        } else {
+        }
+        if !limited {
             return Err(Error::CategoryLimitExceeded);
         }
 
+        // Reserve the amount until the authorization is executed, cancelled or expires
+        allocation.reserved += amount;
+        for cat in category_path.iter() {
+            let reserved = allocation.category_reserved.get(cat.clone()).unwrap_or(0);
+            allocation.category_reserved.set(cat, reserved + amount);
+        }
+        env.storage().persistent().set(&allocation_key, &allocation);
+        ttl::extend_persistent(&env, &allocation_key);
+
         // Generate authorization ID
-        let auth_counter: u64 = env.storage().instance().get(&AUTH_COUNTER).unwrap_or(0);
+        let auth_counter: u64 = env.storage().instance().get(&DataKey::AuthCounter).unwrap_or(0);
         let auth_id = auth_counter + 1;
-        env.storage().instance().set(&AUTH_COUNTER, &auth_id);
+        env.storage().instance().set(&DataKey::AuthCounter, &auth_id);
 
-        // Create authorization
+        // Create authorization, lapsing no later than the spending deadline
+        let now = env.ledger().timestamp();
+        let mut expires_at = now + AUTH_VALIDITY_SECS;
+        if campaign.spending_deadline != 0 && campaign.spending_deadline < expires_at {
+            expires_at = campaign.spending_deadline;
+        }
         let authorization = SpendingAuthorization {
             auth_id,
+            campaign_id: campaign_id.clone(),
             beneficiary: beneficiary.clone(),
             merchant: merchant.clone(),
             amount,
             category: category.clone(),
-            created_at: env.ledger().timestamp(),
-            status: String::from_str(&env, "PENDING"),
+            category_path,
+            created_at: now,
+            expires_at,
+            status: AuthStatus::Pending,
         };
 
         // Store authorization
-        let mut auths: Map<u64, SpendingAuthorization> = env.storage()
-            .instance()
-            .get(&AUTHS)
-            .unwrap_or(Map::new(&env));
-        auths.set(auth_id, authorization);
-        env.storage().instance().set(&AUTHS, &auths);
+        env.storage().persistent().set(&DataKey::Auth(auth_id), &authorization);
+        ttl::extend_persistent(&env, &DataKey::Auth(auth_id));
 
         // Emit event
-        let category_symbol = symbol_short!("spending");
-        event::emit_spending_authorized(&env, beneficiary, merchant, category_symbol, amount);
+        event::emit_spending_authorized(
+            &env,
+            auth_id,
+            campaign_id,
+            beneficiary,
+            merchant,
+            category,
+            amount,
+            authorization.expires_at,
+        );
 
         Ok(auth_id)
     }
 
     /// Execute authorized spending
+    ///
+    /// The merchant signs to confirm the goods were handed over.
     pub fn execute_spending(
         env: Env,
         auth_id: u64,
     ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Spending)?;
+        ttl::extend_instance(&env);
+
         // Get authorization
-        let mut auths: Map<u64, SpendingAuthorization> = env.storage()
-            .instance()
-            .get(&AUTHS)
-            .unwrap_or(Map::new(&env));
-        
-        let mut authorization = auths.get(auth_id)
+        let auth_key = DataKey::Auth(auth_id);
+        let mut authorization: SpendingAuthorization = env.storage()
+            .persistent()
+            .get(&auth_key)
             .ok_or(Error::AuthorizationNotFound)?;
 
-        // Check if already executed
-        if authorization.status != String::from_str(&env, "PENDING") {
-            return Err(Error::Unauthorized);
+        // Check if already executed, cancelled or expired
+        if authorization.status != AuthStatus::Pending {
+            return Err(Error::AuthorizationNotPending);
+        }
+        if env.ledger().timestamp() > authorization.expires_at {
+            return Err(Error::AuthorizationExpired);
+        }
+
+        let campaign = Self::require_spendable(&env, &authorization.campaign_id)?;
+        if Self::deadline_passed(&env, campaign.spending_deadline) {
+            return Err(Error::SpendingWindowClosed);
+        }
+
+        // Merchant may have been suspended or lost the category since authorization
+        let merchant_registry: Address = Self::config_address(&env, DataKey::MerchantRegistry)?;
+        let merchant_registry = MerchantRegistryClient::new(&env, &merchant_registry);
+        if !merchant_registry.is_approved_for_category(&authorization.merchant, &authorization.category) {
+            return Err(Error::MerchantNotApproved);
+        }
+
+        // Merchant confirms delivery; beneficiary co-signs if the campaign requires it
+        authorization.merchant.require_auth();
+        if campaign.beneficiary_cosign {
+            authorization.beneficiary.require_auth();
         }
 
         // Get USDC token client
-        let usdc_address: Address = env.storage().instance().get(&USDC_TOKEN).unwrap();
+        let usdc_address: Address = Self::config_address(&env, DataKey::UsdcToken)?;
         let token = TokenClient::new(&env, &usdc_address);
 
         // Get vault address
@@ -375,36 +995,74 @@ impl ReliefVault {
         token.transfer(&vault_address, &authorization.merchant, authorization.amount)?;
 
         // Update beneficiary allocation
-        let mut allocations: Map<Address, BeneficiaryAllocation> = env.storage()
-            .instance()
-            .get(&ALLOCATIONS)
-            .unwrap_or(Map::new(&env));
-        
-        let mut allocation = allocations.get(authorization.beneficiary.clone())
+        let allocation_key = DataKey::Allocation(
+            authorization.campaign_id.clone(),
+            authorization.beneficiary.clone(),
+        );
+        let mut allocation: BeneficiaryAllocation = env.storage()
+            .persistent()
+            .get(&allocation_key)
             .ok_or(Error::BeneficiaryNotWhitelisted)?;
 
         allocation.spent += authorization.amount;
+        allocation.reserved -= authorization.amount;
         
-        // Update category spent
-        let category_spent = allocation.category_spent.get(authorization.category.clone()).unwrap_or(0);
-        allocation.category_spent.set(authorization.category.clone(), category_spent + authorization.amount);
+        // Update category spent along the category path
+        for cat in authorization.category_path.iter() {
+            let category_spent = allocation.category_spent.get(cat.clone()).unwrap_or(0);
+            allocation.category_spent.set(cat.clone(), category_spent + authorization.amount);
+            let category_reserved = allocation.category_reserved.get(cat.clone()).unwrap_or(0);
+            allocation.category_reserved.set(cat, category_reserved - authorization.amount);
+        }
         
-        allocations.set(authorization.beneficiary.clone(), allocation.clone());
-        env.storage().instance().set(&ALLOCATIONS, &allocations);
+        env.storage().persistent().set(&allocation_key, &allocation);
+        ttl::extend_persistent(&env, &allocation_key);
+
+        // Move the spent amount from committed to disbursed
+        let balance_key = DataKey::Balance(authorization.campaign_id.clone());
+        let mut balance: CampaignBalance = env.storage()
+            .persistent()
+            .get(&balance_key)
+            .ok_or(Error::CampaignNotFound)?;
+        balance.committed -= authorization.amount;
+        balance.disbursed += authorization.amount;
+        env.storage().persistent().set(&balance_key, &balance);
+        ttl::extend_persistent(&env, &balance_key);
+
+        // Record the spend in the beneficiary and merchant registries; the
+        // allocation's limits checked above are the ones that apply
+        let beneficiary_registry: Address = Self::config_address(&env, DataKey::BeneficiaryRegistry)?;
+        let recorded = BeneficiaryRegistryClient::new(&env, &beneficiary_registry).try_update_spending(
+            &authorization.beneficiary,
+            &authorization.campaign_id,
+            &authorization.category,
+            &authorization.amount,
+        );
+        if !matches!(recorded, Ok(Ok(()))) {
+            return Err(Error::RegistryUpdateFailed);
+        }
+        let recorded = merchant_registry.try_update_received(
+            &authorization.merchant,
+            &authorization.amount,
+        );
+        if !matches!(recorded, Ok(Ok(()))) {
+            return Err(Error::RegistryUpdateFailed);
+        }
 
         // Mark authorization as executed
-        authorization.status = String::from_str(&env, "EXECUTED");
-        auths.set(auth_id, authorization.clone());
-        env.storage().instance().set(&AUTHS, &auths);
+        authorization.status = AuthStatus::Executed;
+        env.storage().persistent().set(&auth_key, &authorization);
+        ttl::extend_persistent(&env, &auth_key);
 
         // Emit event
         let remaining_balance = allocation.total_amount - allocation.spent;
-        let category_symbol = symbol_short!("txn");
         event::emit_transaction_executed(
             &env,
+            auth_id,
+            authorization.campaign_id,
             authorization.beneficiary,
             authorization.merchant,
-            category_symbol,
+            authorization.category,
             authorization.amount,
             remaining_balance,
         );
@@ -412,74 +1070,1796 @@ impl ReliefVault {
         Ok(())
     }
 
-    /// Get campaign balance
-    pub fn get_campaign_balance(
-        env: Env,
-        campaign_id: String,
-    ) -> i128 {
-        let balances: Map<String, i128> = env.storage()
-            .instance()
-            .get(&BALANCES)
-            .unwrap_or(Map::new(&env));
-        
-        balances.get(campaign_id).unwrap_or(0)
-    }
+    /// Cancel a pending authorization (beneficiary or campaign NGO)
+    pub fn cancel_authorization(env: Env, caller: Address, auth_id: u64) -> Result<(), Error> {
+        ttl::extend_instance(&env);
 
-    /// Get beneficiary allocation
-    pub fn get_beneficiary_balance(
-        env: Env,
-        beneficiary_address: Address,
-    ) -> i128 {
-        let allocations: Map<Address, BeneficiaryAllocation> = env.storage()
-            .instance()
-            .get(&ALLOCATIONS)
-            .unwrap_or(Map::new(&env));
-        
-        if let Some(allocation) = allocations.get(beneficiary_address) {
-            allocation.total_amount - allocation.spent
-        } else {
-            0
+        caller.require_auth();
+
+        let auth_key = DataKey::Auth(auth_id);
+        let mut authorization: SpendingAuthorization = env.storage()
+            .persistent()
+            .get(&auth_key)
+            .ok_or(Error::AuthorizationNotFound)?;
+
+        if authorization.status != AuthStatus::Pending {
+            return Err(Error::AuthorizationNotPending);
         }
-    }
 
-    /// Get category spent amount
+        // Only the beneficiary or the campaign NGO may cancel
+        let campaign = Self::load_campaign(&env, &authorization.campaign_id)?;
+        if caller != authorization.beneficiary && caller != campaign.ngo_address {
+            return Err(Error::Unauthorized);
+        }
+
+        Self::release_reservation(&env, &authorization)?;
+        authorization.status = AuthStatus::Cancelled;
+        env.storage().persistent().set(&auth_key, &authorization);
+        ttl::extend_persistent(&env, &auth_key);
+
+        event::emit_authorization_cancelled(
+            &env,
+            auth_id,
+            authorization.campaign_id,
+            authorization.beneficiary,
+            caller,
+            authorization.amount,
+        );
+
+        Ok(())
+    }
+
+    /// Release the reservation of an authorization past its expiry (anyone)
+    pub fn expire_authorization(env: Env, auth_id: u64) -> Result<(), Error> {
+        ttl::extend_instance(&env);
+
+        let auth_key = DataKey::Auth(auth_id);
+        let mut authorization: SpendingAuthorization = env.storage()
+            .persistent()
+            .get(&auth_key)
+            .ok_or(Error::AuthorizationNotFound)?;
+
+        if authorization.status != AuthStatus::Pending {
+            return Err(Error::AuthorizationNotPending);
+        }
+        if env.ledger().timestamp() <= authorization.expires_at {
+            return Err(Error::AuthorizationNotExpired);
+        }
+
+        Self::release_reservation(&env, &authorization)?;
+        authorization.status = AuthStatus::Expired;
+        env.storage().persistent().set(&auth_key, &authorization);
+        ttl::extend_persistent(&env, &auth_key);
+
+        event::emit_authorization_expired(
+            &env,
+            auth_id,
+            authorization.campaign_id,
+            authorization.beneficiary,
+            authorization.amount,
+        );
+
+        Ok(())
+    }
+
+    /// Get authorization details
+    pub fn get_authorization(env: Env, auth_id: u64) -> Option<SpendingAuthorization> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Auth(auth_id))
+    }
+
+    /// Require beneficiary co-signature on spending execution (campaign NGO only)
+    pub fn set_beneficiary_cosign(
+        env: Env,
+        ngo_address: Address,
+        campaign_id: String,
+        required: bool,
+    ) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Campaigns)?;
+        ttl::extend_instance(&env);
+
+        ngo_address.require_auth();
+
+        let campaign_key = DataKey::Campaign(campaign_id.clone());
+        let mut campaign: Campaign = env.storage()
+            .persistent()
+            .get(&campaign_key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        if campaign.ngo_address != ngo_address {
+            return Err(Error::Unauthorized);
+        }
+
+        campaign.beneficiary_cosign = required;
+        env.storage().persistent().set(&campaign_key, &campaign);
+        ttl::extend_persistent(&env, &campaign_key);
+
+        event::emit_cosign_updated(&env, campaign_id, ngo_address, required);
+
+        Ok(())
+    }
+
+    /// Pause an active campaign (campaign NGO or admin)
+    pub fn pause_campaign(env: Env, caller: Address, campaign_id: String) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Campaigns)?;
+        ttl::extend_instance(&env);
+
+        Self::transition(&env, caller, campaign_id, &[CampaignStatus::Active], CampaignStatus::Paused)
+    }
+
+    /// Resume a paused campaign (campaign NGO or admin)
+    pub fn resume_campaign(env: Env, caller: Address, campaign_id: String) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Campaigns)?;
+        ttl::extend_instance(&env);
+
+        Self::transition(&env, caller, campaign_id, &[CampaignStatus::Paused], CampaignStatus::Active)
+    }
+
+    /// Close a campaign to new donations and allocations (campaign NGO or admin)
+    ///
+    /// The unallocated remainder becomes refundable to donors.
+    pub fn close_campaign(env: Env, caller: Address, campaign_id: String) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Campaigns)?;
+        ttl::extend_instance(&env);
+
+        Self::transition(
+            &env,
+            caller,
+            campaign_id,
+            &[CampaignStatus::Active, CampaignStatus::Paused],
+            CampaignStatus::Closed,
+        )
+    }
+
+    /// Cancel a campaign and stop all spending (campaign NGO or admin)
+    ///
+    /// Everything not yet disbursed, including unspent allocations,
+    /// becomes refundable to donors.
+    pub fn cancel_campaign(env: Env, caller: Address, campaign_id: String) -> Result<(), Error> {
+        pause::require_not_paused(&env, Operation::Campaigns)?;
+        ttl::extend_instance(&env);
+
+        Self::transition(
+            &env,
+            caller,
+            campaign_id,
+            &[CampaignStatus::Active, CampaignStatus::Paused, CampaignStatus::Closed],
+            CampaignStatus::Cancelled,
+        )
+    }
+
+    /// Claim a donor's pro-rata share of a closed or cancelled campaign's refund pool
+    pub fn claim_refund(env: Env, donor: Address, campaign_id: String) -> Result<i128, Error> {
+        ttl::extend_instance(&env);
+
+        donor.require_auth();
+
+        let campaign = Self::load_campaign(&env, &campaign_id)?;
+
+        if campaign.status != CampaignStatus::Closed && campaign.status != CampaignStatus::Cancelled {
+            return Err(Error::RefundNotAvailable);
+        }
+
+        let contribution_key = DataKey::Contribution(campaign_id.clone(), donor.clone());
+        let mut contribution: DonorContribution = env.storage()
+            .persistent()
+            .get(&contribution_key)
+            .ok_or(Error::NothingToRefund)?;
+
+        let balance_key = DataKey::Balance(campaign_id.clone());
+        let mut balance: CampaignBalance = env.storage()
+            .persistent()
+            .get(&balance_key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        let amount = Self::refund_due(&balance, &contribution);
+        if amount <= 0 {
+            return Err(Error::NothingToRefund);
+        }
+
+        // Transfer USDC back to donor
+        let usdc_address: Address = Self::config_address(&env, DataKey::UsdcToken)?;
+        let token = TokenClient::new(&env, &usdc_address);
+        token.transfer(&env.current_contract_address(), &donor, amount)?;
+
+        contribution.refunded += amount;
+        env.storage().persistent().set(&contribution_key, &contribution);
+        ttl::extend_persistent(&env, &contribution_key);
+        balance.refunded += amount;
+        env.storage().persistent().set(&balance_key, &balance);
+        ttl::extend_persistent(&env, &balance_key);
+
+        // Emit event
+        event::emit_refund_claimed(&env, campaign_id, donor, amount);
+
+        Ok(amount)
+    }
+
+    /// Get campaign details
+    pub fn get_campaign(env: Env, campaign_id: String) -> Option<Campaign> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+    }
+
+    /// Get the NGO that owns a campaign
+    pub fn get_campaign_ngo(env: Env, campaign_id: String) -> Option<Address> {
+        Self::get_campaign(env, campaign_id).map(|campaign| campaign.ngo_address)
+    }
+
+    /// Get a donor's contribution to a campaign
+    pub fn get_contribution(
+        env: Env,
+        campaign_id: String,
+        donor: Address,
+    ) -> Option<DonorContribution> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contribution(campaign_id, donor))
+    }
+
+    /// Get the refund a donor could claim right now
+    pub fn get_refundable(
+        env: Env,
+        campaign_id: String,
+        donor: Address,
+    ) -> i128 {
+        let contribution: Option<DonorContribution> = env.storage()
+            .persistent()
+            .get(&DataKey::Contribution(campaign_id.clone(), donor));
+        let balance: Option<CampaignBalance> = env.storage()
+            .persistent()
+            .get(&DataKey::Balance(campaign_id));
+
+        match (balance, contribution) {
+            (Some(balance), Some(contribution)) => Self::refund_due(&balance, &contribution),
+            _ => 0,
+        }
+    }
+
+    /// Get campaign fund breakdown (donated, committed, disbursed)
+    pub fn get_campaign_funds(
+        env: Env,
+        campaign_id: String,
+    ) -> Option<CampaignBalance> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(campaign_id))
+    }
+
+    /// Get campaign funds free for new allocations
+    pub fn get_available_balance(
+        env: Env,
+        campaign_id: String,
+    ) -> i128 {
+        match Self::get_campaign_funds(env, campaign_id) {
+            Some(balance) => balance.available(),
+            None => 0,
+        }
+    }
+
+    /// Get a beneficiary's allocation in a campaign
+    pub fn get_allocation(
+        env: Env,
+        campaign_id: String,
+        beneficiary_address: Address,
+    ) -> Option<BeneficiaryAllocation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id, beneficiary_address))
+    }
+
+    /// Get campaigns in which a beneficiary holds an allocation
+    pub fn get_beneficiary_campaigns(
+        env: Env,
+        beneficiary_address: Address,
+    ) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BenefCampaigns(beneficiary_address))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get beneficiary's remaining balance across all campaigns
+    pub fn get_beneficiary_balance(
+        env: Env,
+        beneficiary_address: Address,
+    ) -> i128 {
+        let mut total: i128 = 0;
+        for allocation in Self::load_allocations(&env, &beneficiary_address).iter() {
+            total += allocation.total_amount - allocation.spent;
+        }
+        total
+    }
+
+    /// Get category spent amount across all campaigns
     pub fn get_category_spent(
         env: Env,
         beneficiary_address: Address,
-        category: String,
+        category: Category,
     ) -> i128 {
-        let allocations: Map<Address, BeneficiaryAllocation> = env.storage()
-            .instance()
-            .get(&ALLOCATIONS)
-            .unwrap_or(Map::new(&env));
-        
-        if let Some(allocation) = allocations.get(beneficiary_address) {
-            allocation.category_spent.get(category).unwrap_or(0)
-        } else {
-            0
+        let category = match category.normalize(&env) {
+            Ok(category) => category,
+            Err(_) => return 0,
+        };
+        let mut total: i128 = 0;
+        for allocation in Self::load_allocations(&env, &beneficiary_address).iter() {
+            total += allocation.category_spent.get(category.clone()).unwrap_or(0);
         }
+        total
     }
 
     /// Get authorization status
+    ///
+    /// Pending authorizations past their expiry report `Expired` even
+    /// before their reservation is released.
     pub fn get_authorization_status(
         env: Env,
         auth_id: u64,
-    ) -> u32 {
-        let auths: Map<u64, SpendingAuthorization> = env.storage()
+    ) -> Option<AuthStatus> {
+        let auth: SpendingAuthorization = env.storage()
+            .persistent()
+            .get(&DataKey::Auth(auth_id))?;
+
+        if auth.status == AuthStatus::Pending && env.ledger().timestamp() > auth.expires_at {
+            Some(AuthStatus::Expired)
+        } else {
+            Some(auth.status)
+        }
+    }
+}
+
+impl ReliefVault {
+    /// Rewrite entries stored under schema `from` in the layout of `from + 1`
+    ///
+    /// Version 2 moves campaigns, balances, allocations and authorizations
+    /// out of the instance maps of version 1 into a persistent entry each,
+    /// and replaces status and mode strings with enums. Migrated campaigns
+    /// have no deadlines, allow overfunding and need no co-signature.
+    /// Unspent allocations count as committed and pending authorizations
+    /// keep their reservation. Categories with invalid names are dropped,
+    /// along with authorizations in them.
+    fn migrate_step(env: &Env, from: u32) {
+        if from != 1 {
+            return;
+        }
+
+        // Configuration moves to its own keys
+        if let Some(admin) = env.storage().instance().get::<_, Address>(&LEGACY_ADMIN) {
+            env.storage().instance().set(&DataKey::Admin, &admin);
+        }
+        if let Some(usdc_token) = env.storage().instance().get::<_, Address>(&LEGACY_USDC_TOKEN) {
+            env.storage().instance().set(&DataKey::UsdcToken, &usdc_token);
+        }
+        if let Some(auth_counter) = env.storage().instance().get::<_, u64>(&LEGACY_AUTH_COUNTER) {
+            env.storage().instance().set(&DataKey::AuthCounter, &auth_counter);
+        }
+
+        let campaigns: Map<String, LegacyCampaign> = env.storage()
             .instance()
-            .get(&AUTHS)
-            .unwrap_or(Map::new(&env));
-        
-        if let Some(auth) = auths.get(auth_id) {
-            if auth.status == String::from_str(&env, "PENDING") {
-                0 // Pending
-            } else if auth.status == String::from_str(&env, "EXECUTED") {
-                1 // Executed
+            .get(&LEGACY_CAMPAIGNS)
+            .unwrap_or(Map::new(env));
+        let balances: Map<String, i128> = env.storage()
+            .instance()
+            .get(&LEGACY_BALANCES)
+            .unwrap_or(Map::new(env));
+        let legacy_allocations: Map<Address, LegacyAllocation> = env.storage()
+            .instance()
+            .get(&LEGACY_ALLOCATIONS)
+            .unwrap_or(Map::new(env));
+        let auths: Map<u64, LegacyAuthorization> = env.storage()
+            .instance()
+            .get(&LEGACY_AUTHS)
+            .unwrap_or(Map::new(env));
+
+        // Campaigns, with balances that so far only counted donations
+        let mut funds: Map<String, CampaignBalance> = Map::new(env);
+        for (campaign_id, legacy) in campaigns.iter() {
+            let status = if legacy.status == String::from_str(env, "PAUSED") {
+                CampaignStatus::Paused
+            } else if legacy.status == String::from_str(env, "CLOSED") {
+                CampaignStatus::Closed
+            } else {
+                CampaignStatus::Active
+            };
+            let campaign = Campaign {
+                campaign_id: campaign_id.clone(),
+                ngo_address: legacy.ngo_address,
+                target_amount: legacy.target_amount,
+                control_mode: Self::legacy_control_mode(env, &legacy.control_mode),
+                created_at: legacy.created_at,
+                status,
+                fundraising_deadline: 0,
+                spending_deadline: 0,
+                funding_policy: FundingPolicy::AllowOverfunding,
+                beneficiary_cosign: false,
+            };
+            let campaign_key = DataKey::Campaign(campaign_id.clone());
+            env.storage().persistent().set(&campaign_key, &campaign);
+            ttl::extend_persistent(env, &campaign_key);
+
+            funds.set(campaign_id.clone(), CampaignBalance {
+                donated: balances.get(campaign_id).unwrap_or(0),
+                committed: 0,
+                disbursed: 0,
+                refund_pool: 0,
+                refunded: 0,
+            });
+        }
+
+        // Allocations, one per beneficiary in version 1
+        let mut allocations: Map<Address, BeneficiaryAllocation> = Map::new(env);
+        for (beneficiary, legacy) in legacy_allocations.iter() {
+            let mut categories: Vec<Category> = Vec::new(env);
+            for name in legacy.categories.iter() {
+                if let Ok(category) = Category::from_string(env, &name) {
+                    categories.push_back(category);
+                }
+            }
+            allocations.set(beneficiary.clone(), BeneficiaryAllocation {
+                beneficiary,
+                campaign_id: legacy.campaign_id,
+                total_amount: legacy.total_amount,
+                spent: legacy.spent,
+                control_mode: Self::legacy_control_mode(env, &legacy.control_mode),
+                categories,
+                category_limits: Self::legacy_category_amounts(env, &legacy.category_limits),
+                category_spent: Self::legacy_category_amounts(env, &legacy.category_spent),
+                reserved: 0,
+                category_reserved: Map::new(env),
+            });
+        }
+
+        // Authorizations, reserving what pending ones hold
+        for (auth_id, legacy) in auths.iter() {
+            let category = match Category::from_string(env, &legacy.category) {
+                Ok(category) => category,
+                Err(_) => continue,
+            };
+            let mut allocation = match allocations.get(legacy.beneficiary.clone()) {
+                Some(allocation) => allocation,
+                None => continue,
+            };
+            let status = if legacy.status == String::from_str(env, "PENDING") {
+                AuthStatus::Pending
+            } else if legacy.status == String::from_str(env, "EXECUTED") {
+                AuthStatus::Executed
             } else {
-                2 // Cancelled
+                AuthStatus::Cancelled
+            };
+            if status == AuthStatus::Pending {
+                allocation.reserved += legacy.amount;
+                let reserved = allocation.category_reserved.get(category.clone()).unwrap_or(0);
+                allocation.category_reserved.set(category.clone(), reserved + legacy.amount);
+                allocations.set(legacy.beneficiary.clone(), allocation.clone());
             }
+
+            let authorization = SpendingAuthorization {
+                auth_id,
+                campaign_id: allocation.campaign_id,
+                beneficiary: legacy.beneficiary,
+                merchant: legacy.merchant,
+                amount: legacy.amount,
+                category: category.clone(),
+                category_path: Vec::from_array(env, [category]),
+                created_at: legacy.created_at,
+                expires_at: legacy.created_at + AUTH_VALIDITY_SECS,
+                status,
+            };
+            let auth_key = DataKey::Auth(auth_id);
+            env.storage().persistent().set(&auth_key, &authorization);
+            ttl::extend_persistent(env, &auth_key);
+        }
+
+        for (beneficiary, allocation) in allocations.iter() {
+            if let Some(mut balance) = funds.get(allocation.campaign_id.clone()) {
+                balance.committed += allocation.total_amount - allocation.spent;
+                balance.disbursed += allocation.spent;
+                funds.set(allocation.campaign_id.clone(), balance);
+            }
+
+            let campaigns_key = DataKey::BenefCampaigns(beneficiary.clone());
+            env.storage().persistent().set(&campaigns_key, &Vec::from_array(env, [allocation.campaign_id.clone()]));
+            ttl::extend_persistent(env, &campaigns_key);
+
+            let allocation_key = DataKey::Allocation(allocation.campaign_id.clone(), beneficiary);
+            env.storage().persistent().set(&allocation_key, &allocation);
+            ttl::extend_persistent(env, &allocation_key);
+        }
+
+        for (campaign_id, balance) in funds.iter() {
+            let balance_key = DataKey::Balance(campaign_id);
+            env.storage().persistent().set(&balance_key, &balance);
+            ttl::extend_persistent(env, &balance_key);
+        }
+
+        for key in [
+            LEGACY_ADMIN,
+            LEGACY_USDC_TOKEN,
+            LEGACY_CAMPAIGNS,
+            LEGACY_BALANCES,
+            LEGACY_ALLOCATIONS,
+            LEGACY_AUTHS,
+            LEGACY_AUTH_COUNTER,
+        ] {
+            env.storage().instance().remove(&key);
+        }
+    }
+
+    /// Address stored under a configuration key, such as a registry
+    ///
+    /// Vaults migrated from schema version 1 have no registries until
+    /// `set_registries` is called.
+    fn config_address(env: &Env, key: DataKey) -> Result<Address, Error> {
+        env.storage().instance().get(&key).ok_or(Error::NotInitialized)
+    }
+
+    /// Control mode stored as a string under schema version 1
+    fn legacy_control_mode(env: &Env, mode: &String) -> ControlMode {
+        if *mode == String::from_str(env, "CONTROLLED") {
+            ControlMode::Controlled
         } else {
-            3 // Not found
+            ControlMode::Direct
+        }
+    }
+
+    /// Per-category amounts keyed by name under schema version 1
+    fn legacy_category_amounts(env: &Env, amounts: &Map<String, i128>) -> Map<Category, i128> {
+        let mut result: Map<Category, i128> = Map::new(env);
+        for (name, amount) in amounts.iter() {
+            if let Ok(category) = Category::from_string(env, &name) {
+                result.set(category, amount);
+            }
+        }
+        result
+    }
+
+    /// Require the stored admin's authorization
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Require the caller's authorization and one of `roles`
+    fn require_role(env: &Env, caller: &Address, roles: &[Role]) -> Result<(), Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::Unauthorized)?;
+        rbac::require_role(env, &admin, caller, roles)
+    }
+
+    /// Move a campaign to a new status if the caller may and the transition is valid
+    fn transition(
+        env: &Env,
+        caller: Address,
+        campaign_id: String,
+        from: &[CampaignStatus],
+        to: CampaignStatus,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let campaign_key = DataKey::Campaign(campaign_id.clone());
+        let mut campaign: Campaign = env.storage()
+            .persistent()
+            .get(&campaign_key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        // Only the campaign NGO or the vault admin may change status
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::Unauthorized)?;
+        if caller != campaign.ngo_address && caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if !from.contains(&campaign.status) {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        let old_status = campaign.status;
+        campaign.status = to;
+        env.storage().persistent().set(&campaign_key, &campaign);
+        ttl::extend_persistent(env, &campaign_key);
+
+        // Set aside the funds donors may reclaim
+        if to == CampaignStatus::Closed || to == CampaignStatus::Cancelled {
+            let balance_key = DataKey::Balance(campaign_id.clone());
+            let mut balance: CampaignBalance = env.storage()
+                .persistent()
+                .get(&balance_key)
+                .ok_or(Error::CampaignNotFound)?;
+            if to == CampaignStatus::Cancelled {
+                // Unspent allocations can no longer be spent
+                balance.committed = 0;
+            }
+            balance.refund_pool = balance.donated - balance.committed - balance.disbursed;
+            env.storage().persistent().set(&balance_key, &balance);
+            ttl::extend_persistent(env, &balance_key);
+        }
+
+        event::emit_campaign_status_changed(env, campaign_id, caller, old_status, to);
+
+        Ok(())
+    }
+
+    /// Donor's pro-rata share of the refund pool not yet claimed
+    fn refund_due(balance: &CampaignBalance, contribution: &DonorContribution) -> i128 {
+        if balance.donated == 0 {
+            return 0;
+        }
+        contribution.amount * balance.refund_pool / balance.donated - contribution.refunded
+    }
+
+    /// Check that allocations in the campaign may currently be spent
+    fn require_spendable(env: &Env, campaign_id: &String) -> Result<Campaign, Error> {
+        let campaign = Self::load_campaign(env, campaign_id)?;
+
+        match campaign.status {
+            CampaignStatus::Active | CampaignStatus::Closed => Ok(campaign),
+            CampaignStatus::Paused | CampaignStatus::Cancelled => Err(Error::CampaignNotActive),
+        }
+    }
+
+    /// Read a campaign and extend it, since it is still in use
+    fn load_campaign(env: &Env, campaign_id: &String) -> Result<Campaign, Error> {
+        let campaign_key = DataKey::Campaign(campaign_id.clone());
+        let campaign: Campaign = env.storage()
+            .persistent()
+            .get(&campaign_key)
+            .ok_or(Error::CampaignNotFound)?;
+        ttl::extend_persistent(env, &campaign_key);
+        Ok(campaign)
+    }
+
+    /// Return an authorization's reserved amount to the beneficiary's allocation
+    fn release_reservation(env: &Env, authorization: &SpendingAuthorization) -> Result<(), Error> {
+        let allocation_key = DataKey::Allocation(
+            authorization.campaign_id.clone(),
+            authorization.beneficiary.clone(),
+        );
+        let mut allocation: BeneficiaryAllocation = env.storage()
+            .persistent()
+            .get(&allocation_key)
+            .ok_or(Error::BeneficiaryNotWhitelisted)?;
+
+        allocation.reserved -= authorization.amount;
+        for cat in authorization.category_path.iter() {
+            let category_reserved = allocation.category_reserved.get(cat.clone()).unwrap_or(0);
+            allocation.category_reserved.set(cat, category_reserved - authorization.amount);
+        }
+        env.storage().persistent().set(&allocation_key, &allocation);
+        ttl::extend_persistent(env, &allocation_key);
+
+        Ok(())
+    }
+
+    /// A category followed by its ancestors in the taxonomy
+    fn category_path(env: &Env, category: &Category) -> Result<Vec<Category>, Error> {
+        let category_registry: Address = Self::config_address(env, DataKey::CategoryRegistry)?;
+        let path = CategoryRegistryClient::new(env, &category_registry).get_path(category);
+        if path.is_empty() {
+            return Err(Error::InvalidCategory);
+        }
+        Ok(path)
+    }
+
+    /// Whether a deadline (0 = none) lies behind the current ledger time
+    fn deadline_passed(env: &Env, deadline: u64) -> bool {
+        deadline != 0 && env.ledger().timestamp() > deadline
+    }
+
+    /// Load every allocation held by a beneficiary
+    fn load_allocations(env: &Env, beneficiary: &Address) -> Vec<BeneficiaryAllocation> {
+        let campaign_ids: Vec<String> = env.storage()
+            .persistent()
+            .get(&DataKey::BenefCampaigns(beneficiary.clone()))
+            .unwrap_or(Vec::new(env));
+
+        let mut result: Vec<BeneficiaryAllocation> = Vec::new(env);
+        for campaign_id in campaign_ids.iter() {
+            if let Some(allocation) = env.storage()
+                .persistent()
+                .get(&DataKey::Allocation(campaign_id, beneficiary.clone()))
+            {
+                result.push_back(allocation);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::beneficiary::{BeneficiaryRegistry, BeneficiaryRegistryClient as Beneficiaries};
+    use crate::merchant::{MerchantRegistry, MerchantRegistryClient as Merchants};
+    use crate::testutils::{MockCategories, MockCategoriesClient};
+    use soroban_sdk::symbol_short;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
+    use soroban_sdk::token::StellarAssetClient;
+    use soroban_sdk::IntoVal;
+
+    /// Stand-in for the three registries: approves every address not denied
+    ///
+    /// Beneficiaries enrolled in a campaign are approved for that campaign only.
+    #[contract]
+    struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn deny(env: Env, address: Address) {
+            env.storage().instance().set(&address, &true);
+        }
+
+        pub fn is_verified(env: Env, ngo_address: Address) -> bool {
+            !env.storage().instance().has(&ngo_address)
+        }
+
+        pub fn enroll(env: Env, beneficiary: Address, campaign_id: String) {
+            env.storage().instance().set(&(symbol_short!("campaign"), beneficiary), &campaign_id);
+        }
+
+        pub fn is_approved_for_campaign(env: Env, beneficiary: Address, campaign_id: String) -> bool {
+            let enrolled: Option<String> = env.storage().instance().get(&(symbol_short!("campaign"), beneficiary.clone()));
+            !env.storage().instance().has(&beneficiary) && enrolled.map_or(true, |id| id == campaign_id)
         }
+
+        pub fn is_approved_for_category(env: Env, merchant_address: Address, _category: Category) -> bool {
+            !env.storage().instance().has(&merchant_address)
+        }
+
+        pub fn update_spending(env: Env, beneficiary: Address, _campaign_id: String, _category: Category, amount: i128) {
+            let key = (symbol_short!("spent"), beneficiary);
+            let spent: i128 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(spent + amount));
+        }
+
+        pub fn update_received(env: Env, merchant_address: Address, amount: i128) {
+            let key = (symbol_short!("received"), merchant_address);
+            let received: i128 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(received + amount));
+        }
+
+        pub fn spent(env: Env, beneficiary: Address) -> i128 {
+            env.storage().instance().get(&(symbol_short!("spent"), beneficiary)).unwrap_or(0)
+        }
+
+        pub fn received(env: Env, merchant_address: Address) -> i128 {
+            env.storage().instance().get(&(symbol_short!("received"), merchant_address)).unwrap_or(0)
+        }
+    }
+
+    fn setup(env: &Env) -> (ReliefVaultClient<'_>, Address, Address, MockRegistryClient<'_>) {
+        let (vault, usdc, ngo, registry, _) = setup_with_categories(env);
+        (vault, usdc, ngo, registry)
+    }
+
+    fn setup_with_categories(
+        env: &Env,
+    ) -> (ReliefVaultClient<'_>, Address, Address, MockRegistryClient<'_>, MockCategoriesClient<'_>) {
+        env.mock_all_auths();
+
+        let admin = Address::generate(env);
+        let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let registry_id = env.register_contract(None, MockRegistry);
+        let registry = MockRegistryClient::new(env, &registry_id);
+        let vault_id = env.register_contract(None, ReliefVault);
+        let vault = ReliefVaultClient::new(env, &vault_id);
+        let categories = MockCategoriesClient::new(env, &env.register_contract(None, MockCategories));
+        vault.initialize(&admin, &usdc, &registry_id, &registry_id, &registry_id, &categories.address);
+
+        let ngo = Address::generate(env);
+        (vault, usdc, ngo, registry, categories)
+    }
+
+    fn fund(env: &Env, usdc: &Address, to: &Address, amount: i128) {
+        StellarAssetClient::new(env, usdc).mint(to, &amount);
+    }
+
+    #[test]
+    fn test_controlled_spending_flow() {
+        let env = Env::default();
+        let (vault, usdc, ngo, registry) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "flood-2026");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        vault.create_campaign(&ngo, &campaign_id, &5_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &1_000);
+        assert_eq!(vault.get_available_balance(&campaign_id), 1_000);
+
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 300);
+        vault.allocate_to_beneficiary(
+            &ngo,
+            &campaign_id,
+            &beneficiary,
+            &500,
+            &controlled,
+            &Vec::from_array(&env, [food.clone()]),
+            &limits,
+        );
+        assert_eq!(vault.get_beneficiary_balance(&beneficiary), 500);
+
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &200, &food);
+        assert_eq!(vault.get_authorization_status(&auth_id), Some(AuthStatus::Pending));
+        vault.execute_spending(&auth_id);
+
+        assert_eq!(vault.get_authorization_status(&auth_id), Some(AuthStatus::Executed));
+        assert_eq!(registry.spent(&beneficiary), 200);
+        assert_eq!(registry.received(&merchant), 200);
+        assert_eq!(vault.get_beneficiary_balance(&beneficiary), 300);
+        assert_eq!(vault.get_category_spent(&beneficiary, &food), 200);
+        assert_eq!(
+            vault.get_campaign_funds(&campaign_id),
+            Some(CampaignBalance { donated: 1_000, committed: 300, disbursed: 200, refund_pool: 0, refunded: 0 })
+        );
+        assert_eq!(vault.get_available_balance(&campaign_id), 500);
+        assert_eq!(
+            vault.try_authorize_spending(&beneficiary, &campaign_id, &merchant, &150, &food),
+            Err(Ok(Error::CategoryLimitExceeded))
+        );
+    }
+
+    #[test]
+    fn test_allocations_in_several_campaigns() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let other_ngo = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 2_000);
+
+        let flood = String::from_str(&env, "flood");
+        let quake = String::from_str(&env, "quake");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 1_000);
+        let categories = Vec::from_array(&env, [food.clone()]);
+
+        vault.create_campaign(&ngo, &flood, &5_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.create_campaign(&other_ngo, &quake, &5_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &flood, &1_000);
+        vault.donate(&donor, &quake, &1_000);
+
+        // Limits must be positive and for an allocated category
+        let mut bad_limits = Map::new(&env);
+        bad_limits.set(food.clone(), 0);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &flood, &beneficiary, &400, &controlled, &categories, &bad_limits),
+            Err(Ok(Error::InvalidAmount))
+        );
+        let mut bad_limits = limits.clone();
+        bad_limits.set(Category::new(&env, "medicine").unwrap(), 100);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &flood, &beneficiary, &400, &controlled, &categories, &bad_limits),
+            Err(Ok(Error::InvalidCategory))
+        );
+
+        vault.allocate_to_beneficiary(&ngo, &flood, &beneficiary, &400, &controlled, &categories, &limits);
+        vault.allocate_to_beneficiary(&other_ngo, &quake, &beneficiary, &600, &controlled, &categories, &limits);
+
+        let auth_id = vault.authorize_spending(&beneficiary, &flood, &merchant, &100, &food);
+        vault.execute_spending(&auth_id);
+
+        assert_eq!(vault.get_beneficiary_campaigns(&beneficiary).len(), 2);
+        assert_eq!(vault.get_beneficiary_balance(&beneficiary), 900);
+        assert_eq!(vault.get_category_spent(&beneficiary, &food), 100);
+        assert_eq!(vault.get_allocation(&flood, &beneficiary).unwrap().spent, 100);
+        assert_eq!(vault.get_allocation(&quake, &beneficiary).unwrap().spent, 0);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &flood, &beneficiary, &100, &controlled, &categories, &limits),
+            Err(Ok(Error::BeneficiaryExists))
+        );
+    }
+
+    #[test]
+    fn test_allocation_limited_to_free_balance() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let donor = Address::generate(&env);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "drought");
+        let controlled = ControlMode::Controlled;
+        let categories = Vec::new(&env);
+        let limits = Map::new(&env);
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &1_000);
+
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &first, &800, &controlled, &categories, &limits);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &campaign_id, &second, &800, &controlled, &categories, &limits),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &second, &200, &controlled, &categories, &limits);
+        assert_eq!(vault.get_available_balance(&campaign_id), 0);
+    }
+
+    #[test]
+    fn test_direct_transfer() {
+        let env = Env::default();
+        let (vault, usdc, ngo, registry) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let allocated = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let direct_id = String::from_str(&env, "cash");
+        let controlled_id = String::from_str(&env, "vouchers");
+        let direct = ControlMode::Direct;
+        let controlled = ControlMode::Controlled;
+        vault.create_campaign(&ngo, &direct_id, &1_000, &direct, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.create_campaign(&ngo, &controlled_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &direct_id, &1_000);
+
+        vault.allocate_to_beneficiary(&ngo, &direct_id, &allocated, &300, &direct, &Vec::new(&env), &Map::new(&env));
+        vault.direct_transfer(&ngo, &direct_id, &allocated, &300);
+        vault.direct_transfer(&ngo, &direct_id, &beneficiary, &250);
+
+        let token = soroban_sdk::token::Client::new(&env, &usdc);
+        assert_eq!(token.balance(&allocated), 300);
+        assert_eq!(token.balance(&beneficiary), 250);
+        assert_eq!(
+            vault.get_campaign_funds(&direct_id),
+            Some(CampaignBalance { donated: 1_000, committed: 0, disbursed: 550, refund_pool: 0, refunded: 0 })
+        );
+        assert_eq!(
+            vault.try_direct_transfer(&ngo, &direct_id, &allocated, &1),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        assert_eq!(
+            vault.try_direct_transfer(&ngo, &direct_id, &beneficiary, &500),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        assert_eq!(
+            vault.try_direct_transfer(&ngo, &controlled_id, &beneficiary, &10),
+            Err(Ok(Error::InvalidControlMode))
+        );
+
+        // Free funds are not paid to anyone the registry has not approved
+        registry.deny(&stranger);
+        assert_eq!(
+            vault.try_direct_transfer(&ngo, &direct_id, &stranger, &100),
+            Err(Ok(Error::BeneficiaryNotWhitelisted))
+        );
+        assert_eq!(token.balance(&stranger), 0);
+    }
+
+    #[test]
+    fn test_campaign_lifecycle() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "storm");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 500);
+        let categories = Vec::from_array(&env, [food.clone()]);
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &600);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &500, &controlled, &categories, &limits);
+
+        assert_eq!(
+            vault.try_pause_campaign(&stranger, &campaign_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        vault.pause_campaign(&ngo, &campaign_id);
+        assert_eq!(
+            vault.try_donate(&donor, &campaign_id, &100),
+            Err(Ok(Error::CampaignNotActive))
+        );
+        assert_eq!(
+            vault.try_authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food),
+            Err(Ok(Error::CampaignNotActive))
+        );
+        assert_eq!(
+            vault.try_pause_campaign(&ngo, &campaign_id),
+            Err(Ok(Error::InvalidStatusTransition))
+        );
+
+        vault.resume_campaign(&ngo, &campaign_id);
+        vault.close_campaign(&ngo, &campaign_id);
+        assert_eq!(vault.get_campaign(&campaign_id).unwrap().status, CampaignStatus::Closed);
+        assert_eq!(
+            vault.try_donate(&donor, &campaign_id, &100),
+            Err(Ok(Error::CampaignNotActive))
+        );
+
+        // Existing allocations remain spendable after close
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food);
+        vault.execute_spending(&auth_id);
+        assert_eq!(
+            vault.try_resume_campaign(&ngo, &campaign_id),
+            Err(Ok(Error::InvalidStatusTransition))
+        );
+    }
+
+    #[test]
+    fn test_cancel_blocks_pending_spending() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let stranger = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "fire");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 500);
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &500);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &500, &controlled, &Vec::from_array(&env, [food.clone()]), &limits);
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food);
+
+        assert_eq!(
+            vault.try_cancel_campaign(&stranger, &campaign_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        vault.cancel_campaign(&ngo, &campaign_id);
+        assert_eq!(
+            vault.try_execute_spending(&auth_id),
+            Err(Ok(Error::CampaignNotActive))
+        );
+    }
+
+    #[test]
+    fn test_refunds_after_close_and_cancel() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let token = soroban_sdk::token::Client::new(&env, &usdc);
+        let donor_a = Address::generate(&env);
+        let donor_b = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor_a, 750);
+        fund(&env, &usdc, &donor_b, 250);
+
+        let campaign_id = String::from_str(&env, "landslide");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 400);
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor_a, &campaign_id, &750);
+        vault.donate(&donor_b, &campaign_id, &250);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &400, &controlled, &Vec::from_array(&env, [food.clone()]), &limits);
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &200, &food);
+        vault.execute_spending(&auth_id);
+
+        assert_eq!(
+            vault.try_claim_refund(&donor_a, &campaign_id),
+            Err(Ok(Error::RefundNotAvailable))
+        );
+
+        // Closing frees the 600 unallocated
+        vault.close_campaign(&ngo, &campaign_id);
+        assert_eq!(vault.claim_refund(&donor_a, &campaign_id), 450);
+        assert_eq!(
+            vault.try_claim_refund(&donor_a, &campaign_id),
+            Err(Ok(Error::NothingToRefund))
+        );
+
+        // Cancelling also releases the 200 still committed
+        vault.cancel_campaign(&ngo, &campaign_id);
+        assert_eq!(vault.get_refundable(&campaign_id, &donor_a), 150);
+        assert_eq!(vault.claim_refund(&donor_a, &campaign_id), 150);
+        assert_eq!(vault.claim_refund(&donor_b, &campaign_id), 200);
+
+        assert_eq!(token.balance(&donor_a), 600);
+        assert_eq!(token.balance(&donor_b), 200);
+        assert_eq!(token.balance(&vault.address), 0);
+        assert_eq!(
+            vault.try_claim_refund(&merchant, &campaign_id),
+            Err(Ok(Error::NothingToRefund))
+        );
+    }
+
+    #[test]
+    fn test_campaign_deadlines_and_target() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 2_000);
+        env.ledger().set_timestamp(1_000);
+
+        let campaign_id = String::from_str(&env, "cyclone");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 500);
+        let categories = Vec::from_array(&env, [food.clone()]);
+
+        assert_eq!(
+            vault.try_create_campaign(&ngo, &campaign_id, &1_000, &controlled, &500, &0, &FundingPolicy::StopAtTarget),
+            Err(Ok(Error::InvalidDeadline))
+        );
+        assert_eq!(
+            vault.try_create_campaign(&ngo, &campaign_id, &1_000, &controlled, &3_000, &2_000, &FundingPolicy::StopAtTarget),
+            Err(Ok(Error::InvalidDeadline))
+        );
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &2_000, &3_000, &FundingPolicy::StopAtTarget);
+
+        vault.donate(&donor, &campaign_id, &800);
+        assert_eq!(
+            vault.try_donate(&donor, &campaign_id, &300),
+            Err(Ok(Error::TargetReached))
+        );
+        vault.donate(&donor, &campaign_id, &200);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &500, &controlled, &categories, &limits);
+
+        env.ledger().set_timestamp(2_001);
+        assert_eq!(
+            vault.try_donate(&donor, &campaign_id, &1),
+            Err(Ok(Error::FundraisingEnded))
+        );
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food);
+        vault.execute_spending(&auth_id);
+
+        // Authorizations lapse at the spending deadline
+        let pending = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food);
+        assert_eq!(vault.get_authorization(&pending).unwrap().expires_at, 3_000);
+
+        env.ledger().set_timestamp(3_001);
+        assert_eq!(
+            vault.try_execute_spending(&pending),
+            Err(Ok(Error::AuthorizationExpired))
+        );
+        assert_eq!(
+            vault.try_authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food),
+            Err(Ok(Error::SpendingWindowClosed))
+        );
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &campaign_id, &merchant, &100, &controlled, &categories, &limits),
+            Err(Ok(Error::SpendingWindowClosed))
+        );
+    }
+
+    #[test]
+    fn test_authorization_cancel_and_expiry() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "heatwave");
+        let controlled = ControlMode::Controlled;
+        let water = Category::new(&env, "water").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(water.clone(), 300);
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &1_000);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &300, &controlled, &Vec::from_array(&env, [water.clone()]), &limits);
+
+        // A pending authorization reserves its amount
+        let first = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &200, &water);
+        assert_eq!(
+            vault.try_authorize_spending(&beneficiary, &campaign_id, &merchant, &200, &water),
+            Err(Ok(Error::InsufficientBalance))
+        );
+
+        assert_eq!(
+            vault.try_cancel_authorization(&merchant, &first),
+            Err(Ok(Error::Unauthorized))
+        );
+        vault.cancel_authorization(&beneficiary, &first);
+        assert_eq!(vault.get_authorization_status(&first), Some(AuthStatus::Cancelled));
+        assert_eq!(
+            vault.try_execute_spending(&first),
+            Err(Ok(Error::AuthorizationNotPending))
+        );
+
+        let second = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &300, &water);
+        assert_eq!(
+            vault.try_expire_authorization(&second),
+            Err(Ok(Error::AuthorizationNotExpired))
+        );
+        env.ledger().set_timestamp(AUTH_VALIDITY_SECS + 1);
+        assert_eq!(vault.get_authorization_status(&second), Some(AuthStatus::Expired));
+        assert_eq!(
+            vault.try_execute_spending(&second),
+            Err(Ok(Error::AuthorizationExpired))
+        );
+        vault.expire_authorization(&second);
+        assert_eq!(vault.get_allocation(&campaign_id, &beneficiary).unwrap().reserved, 0);
+
+        let third = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &300, &water);
+        vault.execute_spending(&third);
+        assert_eq!(vault.get_beneficiary_balance(&beneficiary), 0);
+    }
+
+    #[test]
+    fn test_execute_requires_merchant_and_cosigner() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "tsunami");
+        let controlled = ControlMode::Controlled;
+        let shelter = Category::new(&env, "shelter").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(shelter.clone(), 500);
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &1_000);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &500, &controlled, &Vec::from_array(&env, [shelter.clone()]), &limits);
+
+        let first = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &shelter);
+        vault.execute_spending(&first);
+        let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(signers, std::vec![merchant.clone()]);
+
+        vault.set_beneficiary_cosign(&ngo, &campaign_id, &true);
+        let events = env.events().all();
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(topics, (event::COSIGN_UPDATED, campaign_id.clone(), ngo.clone()).into_val(&env));
+        let (_, updated): (u32, event::CosignUpdatedEvent) = data.into_val(&env);
+        assert!(updated.required);
+        let second = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &shelter);
+        vault.execute_spending(&second);
+        let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(signers, std::vec![merchant, beneficiary]);
+    }
+
+    #[test]
+    fn test_registry_checks() {
+        let env = Env::default();
+        let (vault, usdc, ngo, registry) = setup(&env);
+        let unverified_ngo = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let unapproved = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        let rogue_merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+        registry.deny(&unverified_ngo);
+        registry.deny(&unapproved);
+        registry.deny(&rogue_merchant);
+
+        let campaign_id = String::from_str(&env, "mudslide");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 500);
+        let categories = Vec::from_array(&env, [food.clone()]);
+
+        assert_eq!(
+            vault.try_create_campaign(&unverified_ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding),
+            Err(Ok(Error::NGONotVerified))
+        );
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &1_000);
+
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &campaign_id, &unapproved, &100, &controlled, &categories, &limits),
+            Err(Ok(Error::BeneficiaryNotWhitelisted))
+        );
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &500, &controlled, &categories, &limits);
+
+        // Another NGO cannot allocate to a beneficiary approved for this campaign
+        let other_ngo = Address::generate(&env);
+        let other_campaign = String::from_str(&env, "landslide");
+        let enrolled = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+        registry.enroll(&enrolled, &campaign_id);
+        vault.create_campaign(&other_ngo, &other_campaign, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &other_campaign, &1_000);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&other_ngo, &other_campaign, &enrolled, &100, &controlled, &categories, &limits),
+            Err(Ok(Error::BeneficiaryNotWhitelisted))
+        );
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &enrolled, &100, &controlled, &categories, &limits);
+
+        assert_eq!(
+            vault.try_authorize_spending(&beneficiary, &campaign_id, &rogue_merchant, &100, &food),
+            Err(Ok(Error::MerchantNotApproved))
+        );
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food);
+
+        // A merchant suspended after authorization is not paid
+        registry.deny(&merchant);
+        assert_eq!(
+            vault.try_execute_spending(&auth_id),
+            Err(Ok(Error::MerchantNotApproved))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_campaign_rejected() {
+        let env = Env::default();
+        let (vault, _, ngo, _) = setup(&env);
+        let campaign_id = String::from_str(&env, "quake");
+        let direct = ControlMode::Direct;
+
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &direct, &0, &0, &FundingPolicy::AllowOverfunding);
+        assert_eq!(
+            vault.try_create_campaign(&ngo, &campaign_id, &1_000, &direct, &0, &0, &FundingPolicy::AllowOverfunding),
+            Err(Ok(Error::CampaignExists))
+        );
+    }
+
+    /// Vault wired to the real beneficiary and merchant registries
+    fn setup_with_registries(env: &Env) -> (ReliefVaultClient<'_>, Address, Address, Beneficiaries<'_>, Merchants<'_>) {
+        env.mock_all_auths();
+
+        let admin = Address::generate(env);
+        let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let ngo_registry = env.register_contract(None, MockRegistry);
+        let categories = MockCategoriesClient::new(env, &env.register_contract(None, MockCategories));
+        let vault = ReliefVaultClient::new(env, &env.register_contract(None, ReliefVault));
+        let beneficiaries = Beneficiaries::new(env, &env.register_contract(None, BeneficiaryRegistry));
+        let merchants = Merchants::new(env, &env.register_contract(None, MerchantRegistry));
+        beneficiaries.initialize(&admin);
+        beneficiaries.set_vault(&vault.address);
+        beneficiaries.set_category_registry(&categories.address);
+        merchants.initialize(&admin);
+        merchants.set_vault(&vault.address);
+        merchants.set_category_registry(&categories.address);
+        vault.initialize(&admin, &usdc, &ngo_registry, &beneficiaries.address, &merchants.address, &categories.address);
+        (vault, usdc, admin, beneficiaries, merchants)
+    }
+
+    #[test]
+    fn test_spending_recorded_in_registries() {
+        let env = Env::default();
+        let (vault, usdc, admin, beneficiaries, merchants) = setup_with_registries(&env);
+
+        let ngo = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+        let campaign_id = String::from_str(&env, "flood");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let medicine = Category::new(&env, "medicine").unwrap();
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &1_000);
+
+        // The registry approves a lower food limit and no medicine at all
+        beneficiaries.register_for_campaign(&beneficiary, &campaign_id, &String::from_str(&env, "doc"));
+        beneficiaries.approve_beneficiary(&admin, &beneficiary, &campaign_id, &Vec::from_array(&env, [food.clone()]), &Vec::from_array(&env, [300i128]));
+        merchants.register_merchant(&admin, &merchant, &String::from_str(&env, "Shop"), &Vec::from_array(&env, [food.clone(), medicine.clone()]));
+
+        // The allocation's limits are the ones enforced
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 500);
+        limits.set(medicine.clone(), 200);
+        let allocated = Vec::from_array(&env, [food.clone(), medicine.clone()]);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &700, &controlled, &allocated, &limits);
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &400, &food);
+        vault.execute_spending(&auth_id);
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &medicine);
+        vault.execute_spending(&auth_id);
+
+        assert_eq!(beneficiaries.get_category_spent(&beneficiary, &campaign_id, &food), 400);
+        assert_eq!(beneficiaries.get_application_status(&beneficiary, &campaign_id).unwrap().spent, 500);
+        assert_eq!(merchants.get_merchant_info(&merchant).unwrap().total_received, 500);
+        assert_eq!(vault.get_category_spent(&beneficiary, &food), 400);
+
+        // A registry that cannot record the payment fails the spend cleanly
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &50, &food);
+        merchants.pause(&admin, &PauseScope::Only(Operation::Spending));
+        assert_eq!(
+            vault.try_execute_spending(&auth_id),
+            Err(Ok(Error::RegistryUpdateFailed))
+        );
+        assert_eq!(vault.get_authorization_status(&auth_id), Some(AuthStatus::Pending));
+        assert_eq!(merchants.get_merchant_info(&merchant).unwrap().total_received, 500);
+    }
+
+    #[test]
+    fn test_beneficiary_registered_in_two_campaigns() {
+        let env = Env::default();
+        let (vault, usdc, admin, beneficiaries, merchants) = setup_with_registries(&env);
+        let flood_ngo = Address::generate(&env);
+        let quake_ngo = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 2_000);
+        let flood = String::from_str(&env, "flood");
+        let quake = String::from_str(&env, "quake");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let categories = Vec::from_array(&env, [food.clone()]);
+        let doc = String::from_str(&env, "doc");
+        vault.create_campaign(&flood_ngo, &flood, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.create_campaign(&quake_ngo, &quake, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &flood, &1_000);
+        vault.donate(&donor, &quake, &1_000);
+        merchants.register_merchant(&admin, &merchant, &String::from_str(&env, "Shop"), &categories);
+
+        // Each campaign's NGO reviews its own registration
+        beneficiaries.register_for_campaign(&beneficiary, &flood, &doc);
+        beneficiaries.register_for_campaign(&beneficiary, &quake, &doc);
+        assert_eq!(
+            beneficiaries.try_register_for_campaign(&beneficiary, &quake, &doc),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+        beneficiaries.approve_beneficiary(&flood_ngo, &beneficiary, &flood, &categories, &Vec::from_array(&env, [300i128]));
+        assert_eq!(
+            beneficiaries.try_approve_beneficiary(&flood_ngo, &beneficiary, &quake, &categories, &Vec::from_array(&env, [900i128])),
+            Err(Ok(Error::Unauthorized))
+        );
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 200);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&quake_ngo, &quake, &beneficiary, &200, &controlled, &categories, &limits),
+            Err(Ok(Error::BeneficiaryNotWhitelisted))
+        );
+        beneficiaries.approve_beneficiary(&quake_ngo, &beneficiary, &quake, &categories, &Vec::from_array(&env, [200i128]));
+
+        // Both campaigns allocate to the same person and track spending apart
+        vault.allocate_to_beneficiary(&quake_ngo, &quake, &beneficiary, &200, &controlled, &categories, &limits);
+        limits.set(food.clone(), 300);
+        vault.allocate_to_beneficiary(&flood_ngo, &flood, &beneficiary, &300, &controlled, &categories, &limits);
+        let auth_id = vault.authorize_spending(&beneficiary, &flood, &merchant, &120, &food);
+        vault.execute_spending(&auth_id);
+        let auth_id = vault.authorize_spending(&beneficiary, &quake, &merchant, &50, &food);
+        vault.execute_spending(&auth_id);
+
+        assert_eq!(beneficiaries.get_category_spent(&beneficiary, &flood, &food), 120);
+        assert_eq!(beneficiaries.get_category_spent(&beneficiary, &quake, &food), 50);
+        assert_eq!(beneficiaries.get_category_balance(&beneficiary, &quake, &food), 150);
+        assert_eq!(beneficiaries.get_approved_beneficiaries(&flood), Vec::from_array(&env, [beneficiary.clone()]));
+
+        // Revoking one registration leaves the other in place
+        beneficiaries.revoke_beneficiary(&flood_ngo, &beneficiary, &flood);
+        assert!(!beneficiaries.is_approved_for_campaign(&beneficiary, &flood));
+        assert!(beneficiaries.is_approved_for_campaign(&beneficiary, &quake));
+        assert!(beneficiaries.is_approved(&beneficiary));
+    }
+
+    #[test]
+    fn test_parent_limit_covers_children() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _, categories) = setup_with_categories(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "drought");
+        let controlled = ControlMode::Controlled;
+        let food = Category::new(&env, "food").unwrap();
+        let formula = Category::new(&env, "infant_formula").unwrap();
+        let fuel = Category::new(&env, "fuel").unwrap();
+        categories.set_parent(&formula, &food);
+        categories.retire(&fuel);
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &controlled, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &1_000);
+
+        let mut limits = Map::new(&env);
+        limits.set(fuel.clone(), 100);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &500, &controlled, &Vec::from_array(&env, [fuel.clone()]), &limits),
+            Err(Ok(Error::InvalidCategory))
+        );
+        let mut limits = Map::new(&env);
+        limits.set(food.clone(), 300);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &500, &controlled, &Vec::from_array(&env, [food.clone()]), &limits);
+
+        // Only allocated categories and their children may be spent on
+        assert_eq!(
+            vault.try_authorize_spending(&beneficiary, &campaign_id, &merchant, &50, &Category::new(&env, "medicine").unwrap()),
+            Err(Ok(Error::InvalidCategory))
+        );
+
+        // Formula spending counts against the food limit
+        let auth_id = vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &200, &formula);
+        assert_eq!(
+            vault.try_authorize_spending(&beneficiary, &campaign_id, &merchant, &150, &food),
+            Err(Ok(Error::CategoryLimitExceeded))
+        );
+        vault.execute_spending(&auth_id);
+        assert_eq!(vault.get_category_spent(&beneficiary, &food), 200);
+        assert_eq!(vault.get_category_spent(&beneficiary, &formula), 200);
+        vault.authorize_spending(&beneficiary, &campaign_id, &merchant, &100, &food);
+    }
+
+    #[test]
+    fn test_emergency_pause() {
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let admin = vault.get_admin().unwrap();
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let campaign_id = String::from_str(&env, "cyclone");
+        let direct = ControlMode::Direct;
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &direct, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &600);
+
+        // Pausing donations leaves allocations running
+        let donations = PauseScope::Only(Operation::Donations);
+        vault.pause(&admin, &donations);
+        assert!(vault.is_paused(&donations));
+        assert!(!vault.is_paused(&PauseScope::All));
+        assert_eq!(
+            vault.try_donate(&donor, &campaign_id, &100),
+            Err(Ok(Error::ContractPaused))
+        );
+        vault.direct_transfer(&ngo, &campaign_id, &beneficiary, &100);
+
+        // Pausing spending also stops direct payouts
+        let spending = PauseScope::Only(Operation::Spending);
+        vault.pause(&admin, &spending);
+        assert_eq!(
+            vault.try_direct_transfer(&ngo, &campaign_id, &beneficiary, &100),
+            Err(Ok(Error::ContractPaused))
+        );
+        vault.unpause(&admin, &spending);
+
+        // A full pause blocks everything but queries and refunds
+        vault.pause(&admin, &PauseScope::All);
+        vault.unpause(&admin, &donations);
+        assert!(vault.is_paused(&donations));
+        assert_eq!(
+            vault.try_direct_transfer(&ngo, &campaign_id, &beneficiary, &100),
+            Err(Ok(Error::ContractPaused))
+        );
+        assert_eq!(vault.try_pause_campaign(&ngo, &campaign_id), Err(Ok(Error::ContractPaused)));
+        assert_eq!(vault.try_close_campaign(&ngo, &campaign_id), Err(Ok(Error::ContractPaused)));
+        assert_eq!(vault.try_cancel_campaign(&ngo, &campaign_id), Err(Ok(Error::ContractPaused)));
+        assert_eq!(vault.get_campaign(&campaign_id).unwrap().status, CampaignStatus::Active);
+
+        vault.unpause(&admin, &PauseScope::All);
+        assert!(!vault.is_paused(&donations));
+        vault.close_campaign(&ngo, &campaign_id);
+        assert_eq!(vault.claim_refund(&donor, &campaign_id), 500);
+    }
+
+    #[test]
+    fn test_migrate_from_instance_maps() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let vault = ReliefVaultClient::new(&env, &env.register_contract(None, ReliefVault));
+        let ngo = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        let flood = String::from_str(&env, "flood-2026");
+        let quake = String::from_str(&env, "quake-2026");
+        let food = Category::new(&env, "food").unwrap();
+        let medicine = Category::new(&env, "medicine").unwrap();
+
+        // Storage as written by a vault initialized before schema versions
+        env.as_contract(&vault.address, || {
+            let name = |name: &str| String::from_str(&env, name);
+            let mut campaigns: Map<String, LegacyCampaign> = Map::new(&env);
+            for (campaign_id, mode, status) in [(&flood, "CONTROLLED", "ACTIVE"), (&quake, "DIRECT", "PAUSED")] {
+                campaigns.set(campaign_id.clone(), LegacyCampaign {
+                    campaign_id: campaign_id.clone(),
+                    ngo_address: ngo.clone(),
+                    target_amount: 5_000,
+                    control_mode: name(mode),
+                    created_at: 100,
+                    status: name(status),
+                });
+            }
+            let mut category_limits = Map::new(&env);
+            category_limits.set(name("Food"), 400);
+            category_limits.set(name("medicine"), 200);
+            let mut category_spent = Map::new(&env);
+            category_spent.set(name("Food"), 100);
+            let mut allocations: Map<Address, LegacyAllocation> = Map::new(&env);
+            allocations.set(beneficiary.clone(), LegacyAllocation {
+                beneficiary: beneficiary.clone(),
+                campaign_id: flood.clone(),
+                total_amount: 600,
+                spent: 100,
+                control_mode: name("CONTROLLED"),
+                categories: Vec::from_array(&env, [name("Food"), name("medicine"), name("pet food")]),
+                category_limits,
+                category_spent,
+            });
+            let mut auths: Map<u64, LegacyAuthorization> = Map::new(&env);
+            for (auth_id, amount, category, status) in [
+                (1, 100, "Food", "EXECUTED"),
+                (2, 50, "medicine", "PENDING"),
+                (3, 10, "pet food", "PENDING"),
+            ] {
+                auths.set(auth_id, LegacyAuthorization {
+                    auth_id,
+                    beneficiary: beneficiary.clone(),
+                    merchant: merchant.clone(),
+                    amount,
+                    category: name(category),
+                    created_at: 200,
+                    status: name(status),
+                });
+            }
+            let mut balances: Map<String, i128> = Map::new(&env);
+            balances.set(flood.clone(), 1_000);
+
+            env.storage().instance().set(&LEGACY_ADMIN, &admin);
+            env.storage().instance().set(&LEGACY_USDC_TOKEN, &usdc);
+            env.storage().instance().set(&LEGACY_CAMPAIGNS, &campaigns);
+            env.storage().instance().set(&LEGACY_BALANCES, &balances);
+            env.storage().instance().set(&LEGACY_ALLOCATIONS, &allocations);
+            env.storage().instance().set(&LEGACY_AUTHS, &auths);
+            env.storage().instance().set(&LEGACY_AUTH_COUNTER, &3u64);
+        });
+
+        assert_eq!(vault.version(), 1);
+        let intruder = Address::generate(&env);
+        assert_eq!(
+            vault.try_initialize(&intruder, &usdc, &intruder, &intruder, &intruder, &intruder),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+        assert_eq!(vault.migrate(), 2);
+        assert_eq!(vault.migrate(), 2);
+        assert_eq!(vault.get_admin(), Some(admin));
+
+        let campaign = vault.get_campaign(&flood).unwrap();
+        assert_eq!(campaign.control_mode, ControlMode::Controlled);
+        assert_eq!(campaign.status, CampaignStatus::Active);
+        assert_eq!(campaign.funding_policy, FundingPolicy::AllowOverfunding);
+        assert_eq!(campaign.spending_deadline, 0);
+        let paused = vault.get_campaign(&quake).unwrap();
+        assert_eq!(paused.control_mode, ControlMode::Direct);
+        assert_eq!(paused.status, CampaignStatus::Paused);
+        assert_eq!(
+            vault.get_campaign_funds(&flood),
+            Some(CampaignBalance { donated: 1_000, committed: 500, disbursed: 100, refund_pool: 0, refunded: 0 })
+        );
+        assert_eq!(vault.get_campaign_funds(&quake).unwrap().donated, 0);
+
+        let allocation = vault.get_allocation(&flood, &beneficiary).unwrap();
+        assert_eq!(allocation.categories, Vec::from_array(&env, [food.clone(), medicine.clone()]));
+        assert_eq!(allocation.category_limits.get(food.clone()), Some(400));
+        assert_eq!(allocation.reserved, 50);
+        assert_eq!(allocation.category_reserved.get(medicine.clone()), Some(50));
+        assert_eq!(vault.get_beneficiary_campaigns(&beneficiary), Vec::from_array(&env, [flood.clone()]));
+        assert_eq!(vault.get_category_spent(&beneficiary, &food), 100);
+
+        assert_eq!(vault.get_authorization_status(&1), Some(AuthStatus::Executed));
+        let pending = vault.get_authorization(&2).unwrap();
+        assert_eq!(pending.campaign_id, flood);
+        assert_eq!(pending.category, medicine);
+        assert_eq!(pending.expires_at, 200 + AUTH_VALIDITY_SECS);
+        assert_eq!(pending.status, AuthStatus::Pending);
+        assert_eq!(vault.get_authorization(&3), None);
+
+        // Registries are unset until the admin configures them
+        let newcomer = Address::generate(&env);
+        let foods = Vec::from_array(&env, [food.clone()]);
+        assert_eq!(
+            vault.try_allocate_to_beneficiary(&ngo, &flood, &newcomer, &100, &ControlMode::Controlled, &foods, &Map::new(&env)),
+            Err(Ok(Error::NotInitialized))
+        );
+        let registry = env.register_contract(None, MockRegistry);
+        let categories = env.register_contract(None, MockCategories);
+        vault.set_registries(&registry, &registry, &registry, &categories);
+        vault.allocate_to_beneficiary(&ngo, &flood, &newcomer, &100, &ControlMode::Controlled, &foods, &Map::new(&env));
+        assert_eq!(vault.get_available_balance(&flood), 300);
+    }
+
+    #[test]
+    fn test_ttl_extension_and_bumps() {
+        use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        let config = TtlConfig {
+            instance_threshold: 10 * ttl::DAY_IN_LEDGERS,
+            instance_extend_to: 60 * ttl::DAY_IN_LEDGERS,
+            persistent_threshold: 100 * ttl::DAY_IN_LEDGERS,
+            persistent_extend_to: 120 * ttl::DAY_IN_LEDGERS,
+        };
+        let backwards = TtlConfig { persistent_threshold: 130 * ttl::DAY_IN_LEDGERS, ..config.clone() };
+        assert_eq!(vault.try_set_ttl_config(&backwards), Err(Ok(Error::InvalidTtlConfig)));
+        vault.set_ttl_config(&config);
+        assert_eq!(vault.get_ttl_config(), config);
+
+        // Writes extend the entries they touch
+        let campaign_id = String::from_str(&env, "cyclone");
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &ControlMode::Direct, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &600);
+        vault.allocate_to_beneficiary(&ngo, &campaign_id, &beneficiary, &100, &ControlMode::Direct, &Vec::new(&env), &Map::new(&env));
+        let campaign_key = DataKey::Campaign(campaign_id.clone());
+        let allocation_key = DataKey::Allocation(campaign_id.clone(), beneficiary.clone());
+        let ttl_of = |key: &DataKey| env.as_contract(&vault.address, || env.storage().persistent().get_ttl(key));
+        assert_eq!(ttl_of(&campaign_key), config.persistent_extend_to);
+
+        // The instance is still above its new threshold, so it keeps the default lifetime
+        assert_eq!(
+            env.as_contract(&vault.address, || env.storage().instance().get_ttl()),
+            TtlConfig::default_config().instance_extend_to
+        );
+
+        // Anyone can top up records that dropped below the threshold
+        env.ledger().with_mut(|li| li.sequence_number += 25 * ttl::DAY_IN_LEDGERS);
+        assert_eq!(ttl_of(&campaign_key), 95 * ttl::DAY_IN_LEDGERS);
+        vault.bump_campaign(&campaign_id);
+        assert_eq!(vault.bump_beneficiary(&beneficiary), 1);
+        assert_eq!(ttl_of(&campaign_key), config.persistent_extend_to);
+        assert_eq!(ttl_of(&allocation_key), config.persistent_extend_to);
+        assert_eq!(
+            vault.try_bump_campaign(&String::from_str(&env, "unknown")),
+            Err(Ok(Error::CampaignNotFound))
+        );
+    }
+
+    #[test]
+    fn test_reads_extend_campaigns() {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let env = Env::default();
+        let (vault, usdc, ngo, _) = setup(&env);
+        let donor = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        fund(&env, &usdc, &donor, 1_000);
+
+        // Top up on every access, so any extension shows
+        let config = TtlConfig {
+            persistent_threshold: 120 * ttl::DAY_IN_LEDGERS,
+            ..TtlConfig::default_config()
+        };
+        vault.set_ttl_config(&config);
+
+        let campaign_id = String::from_str(&env, "cyclone");
+        vault.create_campaign(&ngo, &campaign_id, &1_000, &ControlMode::Direct, &0, &0, &FundingPolicy::AllowOverfunding);
+        vault.donate(&donor, &campaign_id, &600);
+        let campaign_key = DataKey::Campaign(campaign_id.clone());
+        let contribution_key = DataKey::Contribution(campaign_id.clone(), donor.clone());
+        let ttl_of = |key: &DataKey| env.as_contract(&vault.address, || env.storage().persistent().get_ttl(key));
+
+        // Donations and payouts only read the campaign, but still extend it
+        env.ledger().with_mut(|li| li.sequence_number += 1_000);
+        vault.donate(&donor, &campaign_id, &100);
+        assert_eq!(ttl_of(&campaign_key), config.persistent_extend_to);
+        env.ledger().with_mut(|li| li.sequence_number += 1_000);
+        vault.direct_transfer(&ngo, &campaign_id, &beneficiary, &50);
+        assert_eq!(ttl_of(&campaign_key), config.persistent_extend_to);
+
+        // Contributions can be topped up by anyone until they are refunded
+        assert_eq!(ttl_of(&contribution_key), config.persistent_extend_to - 1_000);
+        vault.bump_contribution(&campaign_id, &donor);
+        assert_eq!(ttl_of(&contribution_key), config.persistent_extend_to);
+        assert_eq!(
+            vault.try_bump_contribution(&campaign_id, &beneficiary),
+            Err(Ok(Error::NothingToRefund))
+        );
     }
 }